@@ -15,6 +15,7 @@ import (
 	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
 	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
 	sdk "github.com/cosmos/cosmos-sdk/types"
+	authtypes "github.com/cosmos/cosmos-sdk/x/auth/types"
 	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
 	"github.com/cosmos/gogoproto/proto"
 	"github.com/margined-protocol/test-tube/neutron-test-tube/result"
@@ -24,6 +25,13 @@ import (
 	sdkmath "cosmossdk.io/math"
 
 	wasmtypes "github.com/CosmWasm/wasmd/x/wasm/types"
+
+	ibcclienttypes "github.com/cosmos/ibc-go/v8/modules/core/02-client/types"
+	channeltypes "github.com/cosmos/ibc-go/v8/modules/core/04-channel/types"
+
+	adminmoduletypes "github.com/cosmos/admin-module/v2/x/adminmodule/types"
+
+	icqtypes "github.com/neutron-org/neutron/v5/x/interchainqueries/types"
 )
 
 var (
@@ -137,6 +145,18 @@ func InitAccount(envId uint64, coinsJson string) *C.char {
 
 // Core function to adjust block time and finalize
 func finalizeWithTime(envId uint64, txBytes [][]byte, seconds uint64) *C.char {
+	bz, err := tryFinalizeWithTime(envId, txBytes, seconds)
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
+}
+
+// Same as finalizeWithTime, but returns the error of a block that cannot be
+// finalized (e.g. when an upgrade without handler is reached) so that it can
+// be retried at the same height.
+func tryFinalizeWithTime(envId uint64, txBytes [][]byte, seconds uint64) ([]byte, error) {
 
 	env := loadEnv(envId)
 	mu.Lock()
@@ -156,7 +176,7 @@ func finalizeWithTime(envId uint64, txBytes [][]byte, seconds uint64) *C.char {
 	// Finalize the block
 	res, err := env.App.FinalizeBlock(reqFinalizeBlock)
 	if err != nil {
-		panic(err)
+		return nil, err
 	}
 	_, err = env.App.Commit()
 	if err != nil {
@@ -170,7 +190,7 @@ func finalizeWithTime(envId uint64, txBytes [][]byte, seconds uint64) *C.char {
 	}
 	envRegister.Store(envId, env)
 
-	return encodeBytesResultBytes(bz)
+	return bz, nil
 }
 
 // Helper function to create transaction bytes based on block height
@@ -182,10 +202,15 @@ func getTxBytes(env *testenv.TestEnv, reqDeliverTxBytes []byte, additionalBytes
 }
 
 //export IncreaseTime
-func IncreaseTime(envId uint64, seconds uint64) {
+func IncreaseTime(envId uint64, seconds uint64) *C.char {
+	return AdvanceBlock(envId, seconds)
+}
+
+//export AdvanceBlock
+func AdvanceBlock(envId uint64, seconds uint64) *C.char {
 	env := loadEnv(envId)
 
-	finalizeWithTime(envId, getTxBytes(&env, nil, nil), seconds)
+	return finalizeOrError(envId, getTxBytes(&env, nil, nil), seconds)
 }
 
 //export FinalizeBlock
@@ -197,7 +222,18 @@ func FinalizeBlock(envId uint64, base64ReqDeliverTx string) *C.char {
 		panic(err)
 	}
 
-	return finalizeWithTime(envId, getTxBytes(&env, reqDeliverTxBytes, nil), 3)
+	return finalizeOrError(envId, getTxBytes(&env, reqDeliverTxBytes, nil), 3)
+}
+
+// Same as finalizeWithTime, but returns the error as an ExecuteError result
+// instead of panicking, e.g. while halted at an upgrade plan.
+func finalizeOrError(envId uint64, txBytes [][]byte, seconds uint64) *C.char {
+	bz, err := tryFinalizeWithTime(envId, txBytes, seconds)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	return encodeBytesResultBytes(bz)
 }
 
 //export SetSlinkyPrices
@@ -264,6 +300,284 @@ func WasmSudo(envId uint64, bech32Address, msgJson string) *C.char {
 	return encodeBytesResultBytes(res)
 }
 
+//export ExecuteAsAdmin
+func ExecuteAsAdmin(envId uint64, base64ProposalBytes string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	proposalBytes, err := base64.StdEncoding.DecodeString(base64ProposalBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	var proposal adminmoduletypes.MsgSubmitProposal
+	if err := env.App.AppCodec().Unmarshal(proposalBytes, &proposal); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	responses, err := env.ExecuteAdminProposal(proposal)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	bz, err := proto.Marshal(&sdk.TxMsgData{MsgResponses: responses})
+	if err != nil {
+		panic(err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes(bz)
+}
+
+//export AdminProposalMessage
+func AdminProposalMessage(envId uint64, msgJson string) *C.char {
+	env := loadEnv(envId)
+
+	msg, err := env.AdminProposalMessage(msgJson)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	bz, err := proto.Marshal(msg)
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
+}
+
+//export SubmitKVQueryResult
+func SubmitKVQueryResult(envId uint64, queryId uint64, base64QueryResultBytes string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	resultBytes, err := base64.StdEncoding.DecodeString(base64QueryResultBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	var queryResult icqtypes.QueryResult
+	if err := env.App.AppCodec().Unmarshal(resultBytes, &queryResult); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := env.SubmitKVQueryResult(queryId, queryResult); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export SubmitTxQueryResult
+func SubmitTxQueryResult(envId uint64, queryId uint64, revisionNumber uint64, revisionHeight uint64, base64Data string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	data, err := base64.StdEncoding.DecodeString(base64Data)
+	if err != nil {
+		panic(err)
+	}
+
+	height := ibcclienttypes.NewHeight(revisionNumber, revisionHeight)
+	if err := env.SubmitTxQueryResult(queryId, height, data); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export CreateMockConnection
+func CreateMockConnection(envId uint64, counterpartyChainId string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	connectionId, err := env.CreateMockConnection(counterpartyChainId)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte(connectionId))
+}
+
+//export OpenAckChannel
+func OpenAckChannel(envId uint64, portId, channelId string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	if err := env.OpenAckChannel(portId, channelId); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export OpenChannel
+func OpenChannel(envId uint64, portId, connectionId, version string, ordered bool) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	ordering := channeltypes.UNORDERED
+	if ordered {
+		ordering = channeltypes.ORDERED
+	}
+
+	channelId, err := env.OpenChannel(portId, connectionId, version, ordering)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte(channelId))
+}
+
+//export AcknowledgePacket
+func AcknowledgePacket(envId uint64, packetJson, base64Ack, relayer string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	packet := parsePacket(packetJson)
+
+	ack, err := base64.StdEncoding.DecodeString(base64Ack)
+	if err != nil {
+		panic(err)
+	}
+
+	relayerAddr, err := sdk.AccAddressFromBech32(relayer)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := env.AcknowledgePacket(packet.ToIBC(), ack, relayerAddr); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export TimeoutPacket
+func TimeoutPacket(envId uint64, packetJson, relayer string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	packet := parsePacket(packetJson)
+
+	relayerAddr, err := sdk.AccAddressFromBech32(relayer)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := env.TimeoutPacket(packet.ToIBC(), relayerAddr); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export ReceivePacket
+func ReceivePacket(envId uint64, packetJson, relayer string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	packet := parsePacket(packetJson)
+
+	relayerAddr, err := sdk.AccAddressFromBech32(relayer)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	ack, err := env.ReceivePacket(packet.ToIBC(), relayerAddr)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes(ack)
+}
+
+// Helper to parse a JSON packet sent from Rust
+func parsePacket(packetJson string) testenv.Packet {
+	var packet testenv.Packet
+	if err := json.Unmarshal([]byte(packetJson), &packet); err != nil {
+		panic(err)
+	}
+	return packet
+}
+
+//export SetDenomMetadata
+func SetDenomMetadata(envId uint64, base64MetadataBytes string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	metadataBytes, err := base64.StdEncoding.DecodeString(base64MetadataBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	var metadata banktypes.Metadata
+	if err := env.App.AppCodec().Unmarshal(metadataBytes, &metadata); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := env.SetDenomMetadata(metadata); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export SetUpgradeHandler
+func SetUpgradeHandler(envId uint64, name string) {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	env.SetUpgradeHandler(name)
+}
+
+//export GetModuleAddress
+func GetModuleAddress(envId uint64, moduleName string) *C.char {
+	_ = loadEnv(envId)
+	return C.CString(authtypes.NewModuleAddress(moduleName).String())
+}
+
 //export Query
 func Query(envId uint64, path, base64QueryMsgBytes string) *C.char {
 	env := loadEnv(envId)
@@ -289,6 +603,39 @@ func Query(envId uint64, path, base64QueryMsgBytes string) *C.char {
 	return encodeBytesResultBytes(res.Value)
 }
 
+//export QueryAtHeight
+func QueryAtHeight(envId uint64, height int64, path, base64QueryMsgBytes string) *C.char {
+	env := loadEnv(envId)
+	queryMsgBytes, err := base64.StdEncoding.DecodeString(base64QueryMsgBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	// every committed version is retained by the in-memory store, see `NewNeutronApp`
+	cms, err := env.App.CommitMultiStore().CacheMultiStoreWithVersion(height)
+	if err != nil {
+		return encodeErrToResultBytes(result.QueryError, fmt.Errorf("no state at height %d: %w", height, err))
+	}
+	ctx := env.Ctx.WithMultiStore(cms).WithBlockHeight(height)
+
+	req := abci.RequestQuery{}
+	req.Data = queryMsgBytes
+	req.Height = height
+
+	route := env.App.GRPCQueryRouter().Route(path)
+	if route == nil {
+		err := errors.New("No route found for `" + path + "`")
+		return encodeErrToResultBytes(result.QueryError, err)
+	}
+	res, err := route(ctx, &req)
+
+	if err != nil {
+		return encodeErrToResultBytes(result.QueryError, err)
+	}
+
+	return encodeBytesResultBytes(res.Value)
+}
+
 //export GetBlockTime
 func GetBlockTime(envId uint64) int64 {
 	env := loadEnv(envId)
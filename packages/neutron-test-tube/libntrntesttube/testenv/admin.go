@@ -0,0 +1,82 @@
+package testenv
+
+import (
+	"fmt"
+
+	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdktx "github.com/cosmos/cosmos-sdk/types/tx"
+	authtypes "github.com/cosmos/cosmos-sdk/x/auth/types"
+	"github.com/pkg/errors"
+
+	adminmoduletypes "github.com/cosmos/admin-module/v2/x/adminmodule/types"
+)
+
+// ExecuteAdminProposal runs the messages of an admin proposal the same way the
+// adminmodule does: every message must be signed by the adminmodule account and
+// the whole proposal is applied atomically. Unlike the tx path, the response of
+// each message is returned so that callers can decode it.
+//
+// The proposal is first submitted to the adminmodule `MsgSubmitProposal` handler
+// in a discarded context, so that it is rejected exactly as on chain: proposer not
+// an admin, wrong signer or message type outside of the Neutron allowlist.
+func (env *TestEnv) ExecuteAdminProposal(proposal adminmoduletypes.MsgSubmitProposal) ([]*codectypes.Any, error) {
+	submitProposal := env.App.MsgServiceRouter().Handler(&proposal)
+	if submitProposal == nil {
+		return nil, fmt.Errorf("unrecognized message type %s", sdk.MsgTypeURL(&proposal))
+	}
+	submitCtx, _ := env.Ctx.CacheContext()
+	if _, err := submitProposal(submitCtx, &proposal); err != nil {
+		return nil, errors.Wrap(err, "admin proposal rejected")
+	}
+
+	msgs, err := sdktx.GetMsgs(proposal.Messages, "sdk.MsgProposal")
+	if err != nil {
+		return nil, err
+	}
+
+	authority := authtypes.NewModuleAddress(adminmoduletypes.ModuleName)
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	responses := make([]*codectypes.Any, 0, len(msgs))
+	for i, msg := range msgs {
+		signers, _, err := env.App.AppCodec().GetMsgV1Signers(msg)
+		if err != nil {
+			return nil, errors.Wrapf(err, "message %d", i)
+		}
+		if len(signers) != 1 || !authority.Equals(sdk.AccAddress(signers[0])) {
+			return nil, fmt.Errorf("message %d: expected %s as only signer", i, authority)
+		}
+
+		handler := env.App.MsgServiceRouter().Handler(msg)
+		if handler == nil {
+			return nil, fmt.Errorf("message %d: unrecognized message type %s", i, sdk.MsgTypeURL(msg))
+		}
+
+		res, err := handler(cacheCtx, msg)
+		if err != nil {
+			return nil, errors.Wrapf(err, "message %d", i)
+		}
+
+		if len(res.MsgResponses) > 0 {
+			responses = append(responses, res.MsgResponses[0])
+		} else {
+			responses = append(responses, &codectypes.Any{TypeUrl: sdk.MsgTypeURL(msg) + "Response"})
+		}
+	}
+
+	write()
+
+	return responses, nil
+}
+
+// AdminProposalMessage decodes the JSON encoded message of a contract
+// `ProposalExecuteMessage` admin proposal, as the Neutron wasm bindings do.
+func (env *TestEnv) AdminProposalMessage(msgJSON string) (*codectypes.Any, error) {
+	var msg sdk.Msg
+	if err := env.App.AppCodec().UnmarshalInterfaceJSON([]byte(msgJSON), &msg); err != nil {
+		return nil, errors.Wrap(err, "failed to unmarshal admin proposal message")
+	}
+
+	return codectypes.NewAnyWithValue(msg)
+}
@@ -0,0 +1,17 @@
+package testenv
+
+import (
+	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
+)
+
+// SetDenomMetadata sets the bank metadata of any denom. Outside of tests only
+// the tokenfactory can set it, and only for the denoms it created.
+func (env *TestEnv) SetDenomMetadata(metadata banktypes.Metadata) error {
+	if err := metadata.Validate(); err != nil {
+		return err
+	}
+
+	env.App.BankKeeper.SetDenomMetaData(env.Ctx, metadata)
+
+	return nil
+}
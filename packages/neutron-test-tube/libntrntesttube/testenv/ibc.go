@@ -0,0 +1,337 @@
+package testenv
+
+import (
+	"bytes"
+	"fmt"
+	"strings"
+	"time"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	capabilitytypes "github.com/cosmos/ibc-go/modules/capability/types"
+	icatypes "github.com/cosmos/ibc-go/v8/modules/apps/27-interchain-accounts/types"
+	clienttypes "github.com/cosmos/ibc-go/v8/modules/core/02-client/types"
+	connectiontypes "github.com/cosmos/ibc-go/v8/modules/core/03-connection/types"
+	channeltypes "github.com/cosmos/ibc-go/v8/modules/core/04-channel/types"
+	porttypes "github.com/cosmos/ibc-go/v8/modules/core/05-port/types"
+	commitmenttypes "github.com/cosmos/ibc-go/v8/modules/core/23-commitment/types"
+	host "github.com/cosmos/ibc-go/v8/modules/core/24-host"
+	ibcexported "github.com/cosmos/ibc-go/v8/modules/core/exported"
+	ibctm "github.com/cosmos/ibc-go/v8/modules/light-clients/07-tendermint"
+)
+
+// Long enough for the mock client to never expire, whatever time is skipped in tests.
+const mockClientTrustingPeriod = 10 * 365 * 24 * time.Hour
+
+// Packet is the JSON representation of a packet exchanged with the Rust side.
+type Packet struct {
+	Sequence           uint64             `json:"sequence"`
+	SourcePort         string             `json:"source_port"`
+	SourceChannel      string             `json:"source_channel"`
+	DestinationPort    string             `json:"destination_port"`
+	DestinationChannel string             `json:"destination_channel"`
+	Data               []byte             `json:"data"`
+	TimeoutHeight      clienttypes.Height `json:"timeout_height"`
+	TimeoutTimestamp   uint64             `json:"timeout_timestamp"`
+}
+
+func (p Packet) ToIBC() channeltypes.Packet {
+	return channeltypes.NewPacket(
+		p.Data,
+		p.Sequence,
+		p.SourcePort,
+		p.SourceChannel,
+		p.DestinationPort,
+		p.DestinationChannel,
+		p.TimeoutHeight,
+		p.TimeoutTimestamp,
+	)
+}
+
+// CreateMockConnection creates a tendermint client for a counterparty chain that
+// does not exist, and an OPEN connection on top of it. Channels opened on this
+// connection are driven by the mock counterparty helpers below, which skip
+// proof verification.
+func (env *TestEnv) CreateMockConnection(counterpartyChainID string) (string, error) {
+	ibcKeeper := env.App.IBCKeeper
+
+	latestHeight := clienttypes.NewHeight(clienttypes.ParseChainID(counterpartyChainID), 1)
+	clientState := ibctm.NewClientState(
+		counterpartyChainID,
+		ibctm.DefaultTrustLevel,
+		mockClientTrustingPeriod,
+		mockClientTrustingPeriod+24*time.Hour,
+		10*time.Second,
+		latestHeight,
+		commitmenttypes.GetSDKSpecs(),
+		[]string{"upgrade", "upgradedIBCState"},
+	)
+	consensusState := ibctm.NewConsensusState(
+		env.Ctx.BlockTime(),
+		commitmenttypes.NewMerkleRoot([]byte("mock")),
+		bytes.Repeat([]byte{1}, 32),
+	)
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	clientID, err := ibcKeeper.ClientKeeper.CreateClient(cacheCtx, clientState, consensusState)
+	if err != nil {
+		return "", err
+	}
+
+	connectionID := ibcKeeper.ConnectionKeeper.GenerateConnectionIdentifier(cacheCtx)
+	connection := connectiontypes.NewConnectionEnd(
+		connectiontypes.OPEN,
+		clientID,
+		connectiontypes.NewCounterparty(
+			"07-tendermint-0",
+			"connection-0",
+			commitmenttypes.NewMerklePrefix([]byte(ibcexported.StoreKey)),
+		),
+		connectiontypes.GetCompatibleVersions(),
+		0,
+	)
+	ibcKeeper.ConnectionKeeper.SetConnection(cacheCtx, connectionID, connection)
+
+	paths, _ := ibcKeeper.ConnectionKeeper.GetClientConnectionPaths(cacheCtx, clientID)
+	ibcKeeper.ConnectionKeeper.SetClientConnectionPaths(cacheCtx, clientID, append(paths, connectionID))
+
+	write()
+
+	return connectionID, nil
+}
+
+// OpenAckChannel moves a channel from INIT to OPEN as if the counterparty had
+// answered the handshake with MsgChannelOpenTry. For interchain accounts the
+// counterparty version carries the address of the account on the host.
+func (env *TestEnv) OpenAckChannel(portID, channelID string) error {
+	channelKeeper := env.App.IBCKeeper.ChannelKeeper
+
+	channel, found := channelKeeper.GetChannel(env.Ctx, portID, channelID)
+	if !found {
+		return fmt.Errorf("channel %s/%s not found", portID, channelID)
+	}
+	if channel.State != channeltypes.INIT {
+		return fmt.Errorf("channel %s/%s is in state %s, expected %s", portID, channelID, channel.State, channeltypes.INIT)
+	}
+
+	counterpartyVersion := channel.Version
+	if strings.HasPrefix(portID, icatypes.ControllerPortPrefix) {
+		var metadata icatypes.Metadata
+		if err := icatypes.ModuleCdc.UnmarshalJSON([]byte(channel.Version), &metadata); err != nil {
+			return err
+		}
+		metadata.Address = icatypes.GenerateAddress(env.Ctx, channel.ConnectionHops[0], portID).String()
+		counterpartyVersion = string(icatypes.ModuleCdc.MustMarshalJSON(&metadata))
+	}
+
+	// the mock counterparty uses the same channel identifier
+	counterpartyChannelID := channelID
+
+	cbs, _, err := env.channelRoute(portID, channelID)
+	if err != nil {
+		return err
+	}
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	if err := cbs.OnChanOpenAck(cacheCtx, portID, channelID, counterpartyChannelID, counterpartyVersion); err != nil {
+		return err
+	}
+	channelKeeper.WriteOpenAckChannel(cacheCtx, portID, channelID, counterpartyVersion, counterpartyChannelID)
+
+	write()
+
+	return nil
+}
+
+// OpenChannel opens a channel on `portID` over `connectionID`, with the mock
+// counterparty answering the handshake on the same port and channel identifier.
+func (env *TestEnv) OpenChannel(portID, connectionID, version string, ordering channeltypes.Order) (string, error) {
+	ibcKeeper := env.App.IBCKeeper
+
+	module, portCap, err := ibcKeeper.PortKeeper.LookupModuleByPort(env.Ctx, portID)
+	if err != nil {
+		return "", err
+	}
+
+	cbs, ok := ibcKeeper.Router.GetRoute(module)
+	if !ok {
+		return "", fmt.Errorf("no route for module %s", module)
+	}
+
+	connectionHops := []string{connectionID}
+	counterparty := channeltypes.NewCounterparty(portID, "")
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	channelID, chanCap, err := ibcKeeper.ChannelKeeper.ChanOpenInit(cacheCtx, ordering, connectionHops, portID, portCap, counterparty, version)
+	if err != nil {
+		return "", err
+	}
+
+	version, err = cbs.OnChanOpenInit(cacheCtx, ordering, connectionHops, portID, channelID, chanCap, counterparty, version)
+	if err != nil {
+		return "", err
+	}
+	ibcKeeper.ChannelKeeper.WriteOpenInitChannel(cacheCtx, portID, channelID, ordering, connectionHops, counterparty, version)
+
+	write()
+
+	if err := env.OpenAckChannel(portID, channelID); err != nil {
+		return "", err
+	}
+
+	return channelID, nil
+}
+
+// AcknowledgePacket delivers `ack` for a packet sent by this chain, as
+// MsgAcknowledgement does once the proof has been verified.
+func (env *TestEnv) AcknowledgePacket(packet channeltypes.Packet, ack []byte, relayer sdk.AccAddress) error {
+	channelKeeper := env.App.IBCKeeper.ChannelKeeper
+
+	channel, err := env.checkPacketCommitment(packet)
+	if err != nil {
+		return err
+	}
+
+	cbs, _, err := env.channelRoute(packet.SourcePort, packet.SourceChannel)
+	if err != nil {
+		return err
+	}
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	if channel.Ordering == channeltypes.ORDERED {
+		nextSequenceAck, _ := channelKeeper.GetNextSequenceAck(cacheCtx, packet.SourcePort, packet.SourceChannel)
+		if packet.Sequence != nextSequenceAck {
+			return fmt.Errorf("packet sequence %d does not match next sequence ack %d", packet.Sequence, nextSequenceAck)
+		}
+		channelKeeper.SetNextSequenceAck(cacheCtx, packet.SourcePort, packet.SourceChannel, nextSequenceAck+1)
+	}
+
+	store := cacheCtx.KVStore(env.App.GetKey(ibcexported.StoreKey))
+	store.Delete(host.PacketCommitmentKey(packet.SourcePort, packet.SourceChannel, packet.Sequence))
+
+	if err := cbs.OnAcknowledgementPacket(cacheCtx, packet, ack, relayer); err != nil {
+		return err
+	}
+
+	write()
+
+	return nil
+}
+
+// TimeoutPacket times out a packet sent by this chain, as MsgTimeout does once
+// the proof of non-receipt has been verified. Ordered channels are closed.
+func (env *TestEnv) TimeoutPacket(packet channeltypes.Packet, relayer sdk.AccAddress) error {
+	if _, err := env.checkPacketCommitment(packet); err != nil {
+		return err
+	}
+
+	cbs, chanCap, err := env.channelRoute(packet.SourcePort, packet.SourceChannel)
+	if err != nil {
+		return err
+	}
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	if err := cbs.OnTimeoutPacket(cacheCtx, packet, relayer); err != nil {
+		return err
+	}
+	if err := env.App.IBCKeeper.ChannelKeeper.TimeoutExecuted(cacheCtx, chanCap, packet); err != nil {
+		return err
+	}
+
+	write()
+
+	return nil
+}
+
+// ReceivePacket delivers a packet sent by the mock counterparty on
+// `packet.DestinationChannel`, as MsgRecvPacket does once the proof has been
+// verified. The sequence and source of the packet are filled from the channel.
+// The acknowledgement written by the application is returned, nil if it is
+// asynchronous.
+func (env *TestEnv) ReceivePacket(packet channeltypes.Packet, relayer sdk.AccAddress) ([]byte, error) {
+	channelKeeper := env.App.IBCKeeper.ChannelKeeper
+	portID, channelID := packet.DestinationPort, packet.DestinationChannel
+
+	channel, found := channelKeeper.GetChannel(env.Ctx, portID, channelID)
+	if !found {
+		return nil, fmt.Errorf("channel %s/%s not found", portID, channelID)
+	}
+	if channel.State != channeltypes.OPEN {
+		return nil, fmt.Errorf("channel %s/%s is in state %s, expected %s", portID, channelID, channel.State, channeltypes.OPEN)
+	}
+
+	sequence, _ := channelKeeper.GetNextSequenceRecv(env.Ctx, portID, channelID)
+	packet.Sequence = sequence
+	packet.SourcePort = channel.Counterparty.PortId
+	packet.SourceChannel = channel.Counterparty.ChannelId
+
+	cbs, chanCap, err := env.channelRoute(portID, channelID)
+	if err != nil {
+		return nil, err
+	}
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	channelKeeper.SetNextSequenceRecv(cacheCtx, portID, channelID, sequence+1)
+	if channel.Ordering == channeltypes.UNORDERED {
+		channelKeeper.SetPacketReceipt(cacheCtx, portID, channelID, sequence)
+	}
+
+	// state changes of the application are discarded on error acknowledgements
+	appCtx, writeApp := cacheCtx.CacheContext()
+	ack := cbs.OnRecvPacket(appCtx, packet, relayer)
+	if ack == nil || ack.Success() {
+		writeApp()
+	}
+
+	if ack == nil {
+		write()
+		return nil, nil
+	}
+
+	if err := channelKeeper.WriteAcknowledgement(cacheCtx, chanCap, packet, ack); err != nil {
+		return nil, err
+	}
+
+	write()
+
+	return ack.Acknowledgement(), nil
+}
+
+func (env *TestEnv) checkPacketCommitment(packet channeltypes.Packet) (channeltypes.Channel, error) {
+	channelKeeper := env.App.IBCKeeper.ChannelKeeper
+
+	channel, found := channelKeeper.GetChannel(env.Ctx, packet.SourcePort, packet.SourceChannel)
+	if !found {
+		return channel, fmt.Errorf("channel %s/%s not found", packet.SourcePort, packet.SourceChannel)
+	}
+	if channel.State != channeltypes.OPEN {
+		return channel, fmt.Errorf("channel %s/%s is in state %s, expected %s", packet.SourcePort, packet.SourceChannel, channel.State, channeltypes.OPEN)
+	}
+
+	commitment := channelKeeper.GetPacketCommitment(env.Ctx, packet.SourcePort, packet.SourceChannel, packet.Sequence)
+	if !bytes.Equal(commitment, channeltypes.CommitPacket(env.App.AppCodec(), packet)) {
+		return channel, fmt.Errorf("packet commitment not found for %s/%s sequence %d", packet.SourcePort, packet.SourceChannel, packet.Sequence)
+	}
+
+	return channel, nil
+}
+
+func (env *TestEnv) channelRoute(portID, channelID string) (porttypes.IBCModule, *capabilitytypes.Capability, error) {
+	module, chanCap, err := env.App.IBCKeeper.ChannelKeeper.LookupModuleByChannel(env.Ctx, portID, channelID)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	cbs, ok := env.App.IBCKeeper.Router.GetRoute(module)
+	if !ok {
+		return nil, nil, fmt.Errorf("no route for module %s", module)
+	}
+
+	return cbs, chanCap, nil
+}
@@ -0,0 +1,100 @@
+package testenv
+
+import (
+	"encoding/json"
+	"fmt"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	ibcclienttypes "github.com/cosmos/ibc-go/v8/modules/core/02-client/types"
+
+	icqtypes "github.com/neutron-org/neutron/v5/x/interchainqueries/types"
+)
+
+type kvQueryResultSudo struct {
+	KVQueryResult kvQueryResult `json:"kv_query_result"`
+}
+
+type kvQueryResult struct {
+	QueryID uint64 `json:"query_id"`
+}
+
+type txQueryResultSudo struct {
+	TxQueryResult txQueryResult `json:"tx_query_result"`
+}
+
+type txQueryResult struct {
+	QueryID uint64                `json:"query_id"`
+	Height  ibcclienttypes.Height `json:"height"`
+	Data    []byte                `json:"data"`
+}
+
+// SubmitKVQueryResult stores the result of a registered KV query the same way
+// MsgSubmitQueryResult does, without verifying proofs against a light client.
+// The owner's `kv_query_result` sudo handler is called if the result allows
+// KV callbacks.
+func (env *TestEnv) SubmitKVQueryResult(queryID uint64, result icqtypes.QueryResult) error {
+	keeper := &env.App.InterchainQueriesKeeper
+
+	query, err := keeper.GetQueryByID(env.Ctx, queryID)
+	if err != nil {
+		return err
+	}
+	if !icqtypes.InterchainQueryType(query.QueryType).IsKV() {
+		return fmt.Errorf("query %d is not a KV query", queryID)
+	}
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	if err := keeper.SaveKVQueryResult(cacheCtx, queryID, &result); err != nil {
+		return err
+	}
+
+	if result.AllowKvCallbacks {
+		msg := kvQueryResultSudo{KVQueryResult: kvQueryResult{QueryID: queryID}}
+		if err := env.sudo(cacheCtx, query.Owner, msg); err != nil {
+			return err
+		}
+	}
+
+	write()
+
+	return nil
+}
+
+// SubmitTxQueryResult delivers a transaction matching a registered TX query to
+// the owner's `tx_query_result` sudo handler, without verifying its inclusion.
+func (env *TestEnv) SubmitTxQueryResult(queryID uint64, height ibcclienttypes.Height, data []byte) error {
+	query, err := env.App.InterchainQueriesKeeper.GetQueryByID(env.Ctx, queryID)
+	if err != nil {
+		return err
+	}
+	if !icqtypes.InterchainQueryType(query.QueryType).IsTX() {
+		return fmt.Errorf("query %d is not a TX query", queryID)
+	}
+
+	cacheCtx, write := env.Ctx.CacheContext()
+
+	msg := txQueryResultSudo{TxQueryResult: txQueryResult{QueryID: queryID, Height: height, Data: data}}
+	if err := env.sudo(cacheCtx, query.Owner, msg); err != nil {
+		return err
+	}
+
+	write()
+
+	return nil
+}
+
+func (env *TestEnv) sudo(ctx sdk.Context, contract string, msg interface{}) error {
+	contractAddr, err := sdk.AccAddressFromBech32(contract)
+	if err != nil {
+		return err
+	}
+
+	msgBytes, err := json.Marshal(msg)
+	if err != nil {
+		return err
+	}
+
+	_, err = env.App.WasmKeeper.Sudo(ctx, contractAddr, msgBytes)
+	return err
+}
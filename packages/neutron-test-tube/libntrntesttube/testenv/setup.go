@@ -10,6 +10,7 @@ import (
 	// tendermint
 	"cosmossdk.io/log"
 	sdkmath "cosmossdk.io/math"
+	pruningtypes "cosmossdk.io/store/pruning/types"
 
 	// adminmodule
 	adminmoduletypes "github.com/cosmos/admin-module/v2/x/adminmodule/types"
@@ -101,6 +102,8 @@ func NewNeutronApp(nodeHome string) *app.App {
 		NewDebugAppOptionsWithFlagHome(),
 		emptyWasmOpts,
 		baseapp.SetChainID("neutron-666"),
+		// keep every committed version so that past heights can be queried
+		baseapp.SetPruning(pruningtypes.NewPruningOptions(pruningtypes.PruningNothing)),
 	)
 }
 
@@ -0,0 +1,24 @@
+package testenv
+
+import (
+	"context"
+
+	upgradetypes "cosmossdk.io/x/upgrade/types"
+	"github.com/cosmos/cosmos-sdk/types/module"
+)
+
+// SetUpgradeHandler lets the chain resume at the height of the upgrade plan
+// `name`. Handlers registered by the app are kept, otherwise the upgrade only
+// marks the plan as applied without running any migration.
+func (env *TestEnv) SetUpgradeHandler(name string) {
+	if env.App.UpgradeKeeper.HasHandler(name) {
+		return
+	}
+
+	env.App.UpgradeKeeper.SetUpgradeHandler(
+		name,
+		func(_ context.Context, _ upgradetypes.Plan, fromVM module.VersionMap) (module.VersionMap, error) {
+			return fromVM, nil
+		},
+	)
+}
@@ -2,14 +2,21 @@
 
 mod module;
 mod runner;
+#[cfg(test)]
+mod sudo_recorder;
 
 pub use cosmrs;
 pub use margined_neutron_std as neutron_std;
+pub use neutron_sdk;
 
 pub use module::*;
-pub use runner::app::NeutronTestApp;
+pub use runner::app::{NeutronTestApp, BLOCK_TIME_SECONDS};
+pub use runner::ibc::{IbcHeight, IbcPacket, PacketOutcome};
+pub use runner::neutron_msg::neutron_msg_to_any;
+pub use runner::params::ModuleParams;
+pub use runner::replay::{PriceFeedReplay, PricePoint};
 pub use test_tube_ntrn::account::{Account, FeeSetting, NonSigningAccount, SigningAccount};
 pub use test_tube_ntrn::runner::error::{DecodeError, EncodeError, RunnerError};
 pub use test_tube_ntrn::runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
 pub use test_tube_ntrn::runner::Runner;
-pub use test_tube_ntrn::{fn_execute, fn_query};
+pub use test_tube_ntrn::{fn_execute, fn_query, paginate, PAGE_LIMIT};
@@ -1,12 +1,21 @@
+use margined_neutron_std::shim::Any;
 use margined_neutron_std::types::cosmos::adminmodule::adminmodule::{
     MsgAddAdmin, MsgAddAdminResponse, MsgDeleteAdmin, MsgDeleteAdminResponse, MsgSubmitProposal,
     MsgSubmitProposalResponse, QueryAdminsRequest, QueryAdminsResponse,
 };
-use test_tube_ntrn::{fn_execute, fn_query};
+use neutron_sdk::bindings::msg::AdminProposal;
+use prost::Message;
+use test_tube_ntrn::runner::error::{DecodeError, RunnerError};
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query, Account};
 
 use test_tube_ntrn::module::Module;
 use test_tube_ntrn::runner::Runner;
 
+use crate::NeutronTestApp;
+
+pub const ADMIN_MODULE_NAME: &str = "adminmodule";
+
 pub struct Admin<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -37,3 +46,232 @@ where
         pub query_admins ["/cosmos.adminmodule.adminmodule.Query/Admins"]: QueryAdminsRequest => QueryAdminsResponse
     }
 }
+
+impl<'a> Admin<'a, NeutronTestApp> {
+    /// Address of the admin module account, which is the `authority` of
+    /// `MsgUpdateParams` and other privileged messages on Neutron.
+    pub fn authority(&self) -> RunnerResult<String> {
+        self.runner.get_module_address(ADMIN_MODULE_NAME)
+    }
+
+    /// Wrap `msgs` in an admin proposal submitted by the genesis admin (the first validator)
+    /// and execute it immediately. Messages must use [`Admin::authority`] as their signer.
+    ///
+    /// The proposal goes through the admin module handler first, so messages outside of the
+    /// Neutron proposal allowlist are rejected as on chain. It is atomic: if any message fails,
+    /// none of them are applied and the error names the index of the failing message.
+    pub fn execute_as_admin(&self, msgs: Vec<Any>) -> RunnerResult<AdminExecuteResponse> {
+        let admin = self
+            .runner
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)?;
+
+        self.execute_proposal(MsgSubmitProposal {
+            messages: msgs,
+            proposer: admin.address(),
+        })
+    }
+
+    /// Execute the admin proposal a contract submits with `NeutronMsg::SubmitAdminProposal`,
+    /// proposed by `proposer`. As on chain, only `ProposalExecuteMessage` proposals are
+    /// supported and `proposer` must be an admin, e.g. the DAO core contract.
+    pub fn submit_admin_proposal(
+        &self,
+        proposer: &str,
+        admin_proposal: &AdminProposal,
+    ) -> RunnerResult<AdminExecuteResponse> {
+        let message = match admin_proposal {
+            AdminProposal::ProposalExecuteMessage(proposal) => {
+                self.runner.admin_proposal_message(&proposal.message)?
+            }
+            _ => {
+                return Err(RunnerError::ExecuteError {
+                    msg: "only ProposalExecuteMessage admin proposals are supported".to_string(),
+                })
+            }
+        };
+
+        self.execute_proposal(MsgSubmitProposal {
+            messages: vec![Any {
+                type_url: message.type_url,
+                value: message.value,
+            }],
+            proposer: proposer.to_string(),
+        })
+    }
+
+    fn execute_proposal(&self, proposal: MsgSubmitProposal) -> RunnerResult<AdminExecuteResponse> {
+        let msg_responses = self.runner.execute_admin_proposal(&proposal)?;
+
+        Ok(AdminExecuteResponse {
+            msg_responses: msg_responses
+                .into_iter()
+                .map(|res| Any {
+                    type_url: res.type_url,
+                    value: res.value,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Responses of the messages executed through [`Admin::execute_as_admin`] and
+/// [`Admin::submit_admin_proposal`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminExecuteResponse {
+    pub msg_responses: Vec<Any>,
+}
+
+impl AdminExecuteResponse {
+    /// Decode the response of the message at `index`.
+    pub fn decode<R: Message + Default>(&self, index: usize) -> RunnerResult<R> {
+        let res = self
+            .msg_responses
+            .get(index)
+            .ok_or_else(|| RunnerError::GenericError(format!("no response at index {}", index)))?;
+
+        R::decode(res.value.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::shim::Any;
+    use margined_neutron_std::types::cosmos::bank::v1beta1::{
+        MsgSend, MsgUpdateParams, MsgUpdateParamsResponse, Params, QueryParamsRequest,
+        QueryParamsResponse,
+    };
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use neutron_sdk::bindings::msg::{AdminProposal, ProposalExecuteMessage};
+    use prost::Message;
+
+    use crate::{Account, Admin, Bank, NeutronTestApp};
+    use test_tube_ntrn::{Module, Runner};
+
+    fn update_bank_params(authority: String, default_send_enabled: bool) -> Any {
+        Any {
+            type_url: "/cosmos.bank.v1beta1.MsgUpdateParams".to_string(),
+            value: MsgUpdateParams {
+                authority,
+                params: Some(Params {
+                    send_enabled: vec![],
+                    default_send_enabled,
+                }),
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn admin_execute_as_admin() {
+        let app = NeutronTestApp::new();
+        let admin = Admin::new(&app);
+
+        let authority = admin.authority().unwrap();
+        assert_eq!(authority, "neutron1hxskfdxpp5hqgtjj6am6nkjefhfzj359x0ar3z");
+
+        let res = admin
+            .execute_as_admin(vec![update_bank_params(authority, false)])
+            .unwrap();
+        assert_eq!(res.msg_responses.len(), 1);
+        res.decode::<MsgUpdateParamsResponse>(0).unwrap();
+
+        assert!(!bank_params(&app).default_send_enabled);
+
+        // messages not signed by the admin module are rejected
+        let user = app
+            .init_account(&[Coin::new(1_000_000_000u128, "untrn")])
+            .unwrap();
+        admin
+            .execute_as_admin(vec![update_bank_params(user.address(), true)])
+            .unwrap_err();
+        assert!(!bank_params(&app).default_send_enabled);
+
+        // so are messages outside of the proposal allowlist, even if they would succeed
+        Bank::new(&app)
+            .send(
+                MsgSend {
+                    from_address: user.address(),
+                    to_address: authority.clone(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1000".to_string(),
+                    }],
+                },
+                &user,
+            )
+            .unwrap();
+        let err = admin
+            .execute_as_admin(vec![Any {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: MsgSend {
+                    from_address: authority.clone(),
+                    to_address: user.address(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1000".to_string(),
+                    }],
+                }
+                .encode_to_vec(),
+            }])
+            .unwrap_err();
+        assert!(err.to_string().contains("admin proposal rejected"));
+    }
+
+    #[test]
+    fn admin_submit_admin_proposal() {
+        let app = NeutronTestApp::new();
+        let admin = Admin::new(&app);
+
+        let authority = admin.authority().unwrap();
+        let proposal = |default_send_enabled: bool| {
+            AdminProposal::ProposalExecuteMessage(ProposalExecuteMessage {
+                message: format!(
+                    r#"{{"@type":"/cosmos.bank.v1beta1.MsgUpdateParams","authority":"{}","params":{{"send_enabled":[],"default_send_enabled":{}}}}}"#,
+                    authority, default_send_enabled
+                ),
+            })
+        };
+
+        // proposals of accounts that are not admins are rejected
+        let user = app
+            .init_account(&[Coin::new(1_000_000_000u128, "untrn")])
+            .unwrap();
+        let err = admin
+            .submit_admin_proposal(&user.address(), &proposal(false))
+            .unwrap_err();
+        assert!(err.to_string().contains("admin proposal rejected"));
+        assert!(bank_params(&app).default_send_enabled);
+
+        let genesis_admin = app
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)
+            .unwrap();
+        let res = admin
+            .submit_admin_proposal(&genesis_admin.address(), &proposal(false))
+            .unwrap();
+        res.decode::<MsgUpdateParamsResponse>(0).unwrap();
+        assert!(!bank_params(&app).default_send_enabled);
+
+        // messages that are not valid JSON encoded SDK messages cannot be decoded
+        admin
+            .submit_admin_proposal(
+                &genesis_admin.address(),
+                &AdminProposal::ProposalExecuteMessage(ProposalExecuteMessage {
+                    message: "{}".to_string(),
+                }),
+            )
+            .unwrap_err();
+    }
+
+    fn bank_params(app: &NeutronTestApp) -> Params {
+        app.query::<QueryParamsRequest, QueryParamsResponse>(
+            "/cosmos.bank.v1beta1.Query/Params",
+            &QueryParamsRequest {},
+        )
+        .unwrap()
+        .params
+        .unwrap()
+    }
+}
@@ -0,0 +1,205 @@
+use margined_neutron_std::shim::Any;
+use margined_neutron_std::types::cosmos::auth::v1beta1::{
+    AddressBytesToStringRequest, AddressBytesToStringResponse, AddressStringToBytesRequest,
+    AddressStringToBytesResponse, BaseAccount, Bech32PrefixRequest, Bech32PrefixResponse,
+    ModuleAccount, QueryAccountRequest, QueryAccountResponse, QueryAccountsRequest,
+    QueryAccountsResponse, QueryModuleAccountByNameRequest, QueryModuleAccountByNameResponse,
+    QueryModuleAccountsRequest, QueryModuleAccountsResponse, QueryParamsRequest,
+    QueryParamsResponse,
+};
+use prost::Message;
+use test_tube_ntrn::fn_query;
+use test_tube_ntrn::runner::error::{DecodeError, RunnerError};
+use test_tube_ntrn::runner::result::RunnerResult;
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::NeutronTestApp;
+
+pub struct Auth<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Auth<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Auth<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_query! {
+        pub query_account ["/cosmos.auth.v1beta1.Query/Account"]: QueryAccountRequest => QueryAccountResponse
+    }
+
+    fn_query! {
+        pub query_accounts ["/cosmos.auth.v1beta1.Query/Accounts"]: QueryAccountsRequest => QueryAccountsResponse
+    }
+
+    fn_query! {
+        pub query_module_accounts ["/cosmos.auth.v1beta1.Query/ModuleAccounts"]: QueryModuleAccountsRequest => QueryModuleAccountsResponse
+    }
+
+    fn_query! {
+        pub query_module_account_by_name ["/cosmos.auth.v1beta1.Query/ModuleAccountByName"]: QueryModuleAccountByNameRequest => QueryModuleAccountByNameResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.auth.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_bech32_prefix ["/cosmos.auth.v1beta1.Query/Bech32Prefix"]: Bech32PrefixRequest => Bech32PrefixResponse
+    }
+
+    fn_query! {
+        pub query_address_bytes_to_string ["/cosmos.auth.v1beta1.Query/AddressBytesToString"]: AddressBytesToStringRequest => AddressBytesToStringResponse
+    }
+
+    fn_query! {
+        pub query_address_string_to_bytes ["/cosmos.auth.v1beta1.Query/AddressStringToBytes"]: AddressStringToBytesRequest => AddressStringToBytesResponse
+    }
+
+    /// Base account of `address`, also for module accounts.
+    pub fn base_account(&self, address: &str) -> RunnerResult<BaseAccount> {
+        let account = self
+            .query_account(&QueryAccountRequest {
+                address: address.to_string(),
+            })?
+            .account
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("account {} not found", address),
+            })?;
+
+        decode_base_account(account)
+    }
+
+    /// Account number of `address`.
+    pub fn account_number(&self, address: &str) -> RunnerResult<u64> {
+        Ok(self.base_account(address)?.account_number)
+    }
+
+    /// Sequence of the next tx signed by `address`.
+    pub fn sequence(&self, address: &str) -> RunnerResult<u64> {
+        Ok(self.base_account(address)?.sequence)
+    }
+
+    /// Module account registered as `name`, e.g. `fee_collector`.
+    pub fn module_account(&self, name: &str) -> RunnerResult<ModuleAccount> {
+        let account = self
+            .query_module_account_by_name(&QueryModuleAccountByNameRequest {
+                name: name.to_string(),
+            })?
+            .account
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("module account {} not found", name),
+            })?;
+
+        ModuleAccount::decode(account.value.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+}
+
+impl<'a> Auth<'a, NeutronTestApp> {
+    /// Address of the module `name`, whether or not its account was created yet.
+    pub fn module_address(&self, name: &str) -> RunnerResult<String> {
+        self.runner.get_module_address(name)
+    }
+}
+
+fn decode_base_account(account: Any) -> RunnerResult<BaseAccount> {
+    let base_account = match account.type_url.as_str() {
+        "/cosmos.auth.v1beta1.BaseAccount" => BaseAccount::decode(account.value.as_slice()),
+        "/cosmos.auth.v1beta1.ModuleAccount" => ModuleAccount::decode(account.value.as_slice())
+            .map(|module_account| module_account.base_account.unwrap_or_default()),
+        type_url => {
+            return Err(RunnerError::GenericError(format!(
+                "unsupported account type {}",
+                type_url
+            )))
+        }
+    };
+
+    base_account
+        .map_err(DecodeError::ProtoDecodeError)
+        .map_err(RunnerError::DecodeError)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::types::cosmos::auth::v1beta1::{
+        AddressBytesToStringRequest, AddressStringToBytesRequest, Bech32PrefixRequest,
+        QueryModuleAccountsRequest,
+    };
+    use margined_neutron_std::types::cosmos::bank::v1beta1::MsgSend;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+
+    use crate::{Account, Auth, Bank, NeutronTestApp};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn auth_account_introspection() {
+        let app = NeutronTestApp::new();
+        let auth = Auth::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+
+        let first = auth.account_number(&accs[0].address()).unwrap();
+        assert!(auth.account_number(&accs[1].address()).unwrap() > first);
+        assert_eq!(auth.sequence(&accs[0].address()).unwrap(), 0);
+
+        Bank::new(&app)
+            .send(
+                MsgSend {
+                    from_address: accs[0].address(),
+                    to_address: accs[1].address(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1".to_string(),
+                    }],
+                },
+                &accs[0],
+            )
+            .unwrap();
+        assert_eq!(auth.sequence(&accs[0].address()).unwrap(), 1);
+
+        // module addresses are derived from the module name
+        let bonded_pool = auth.module_account("bonded_tokens_pool").unwrap();
+        assert_eq!(
+            bonded_pool.base_account.unwrap().address,
+            auth.module_address("bonded_tokens_pool").unwrap()
+        );
+        assert!(!auth
+            .query_module_accounts(&QueryModuleAccountsRequest {})
+            .unwrap()
+            .accounts
+            .is_empty());
+
+        let prefix = auth
+            .query_bech32_prefix(&Bech32PrefixRequest {})
+            .unwrap()
+            .bech32_prefix;
+        assert_eq!(prefix, "neutron");
+
+        let address_bytes = auth
+            .query_address_string_to_bytes(&AddressStringToBytesRequest {
+                address_string: accs[0].address(),
+            })
+            .unwrap()
+            .address_bytes;
+        let address_string = auth
+            .query_address_bytes_to_string(&AddressBytesToStringRequest { address_bytes })
+            .unwrap()
+            .address_string;
+        assert_eq!(address_string, accs[0].address());
+
+        assert!(auth.sequence("neutron1unknown").is_err());
+    }
+}
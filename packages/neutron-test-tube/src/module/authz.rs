@@ -1,8 +1,20 @@
+use cosmwasm_std::{Coin, Event};
+use margined_neutron_std::shim::{Any, Timestamp};
 use margined_neutron_std::types::cosmos::authz::v1beta1::{
-    MsgExec, MsgExecResponse, MsgGrant, MsgGrantResponse, QueryGranteeGrantsRequest,
-    QueryGranteeGrantsResponse, QueryGranterGrantsRequest, QueryGranterGrantsResponse,
-    QueryGrantsRequest, QueryGrantsResponse,
+    GenericAuthorization, Grant, MsgExec, MsgExecResponse, MsgGrant, MsgGrantResponse, MsgRevoke,
+    MsgRevokeResponse, QueryGranteeGrantsRequest, QueryGranteeGrantsResponse,
+    QueryGranterGrantsRequest, QueryGranterGrantsResponse, QueryGrantsRequest, QueryGrantsResponse,
 };
+use margined_neutron_std::types::cosmos::bank::v1beta1::SendAuthorization;
+use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+use margined_neutron_std::types::cosmwasm::wasm::v1::{
+    AllowAllMessagesFilter, CombinedLimit, ContractExecutionAuthorization, ContractGrant,
+    MaxCallsLimit,
+};
+use prost::Message;
+use test_tube_ntrn::account::{Account, SigningAccount};
+use test_tube_ntrn::runner::error::{DecodeError, RunnerError};
+use test_tube_ntrn::runner::result::{RunnerExecuteResult, RunnerResult};
 use test_tube_ntrn::{fn_execute, fn_query};
 
 use test_tube_ntrn::module::Module;
@@ -30,16 +42,169 @@ where
         pub grant: MsgGrant["/cosmos.authz.v1beta1.MsgGrant"] => MsgGrantResponse
     }
 
+    fn_execute! {
+        pub revoke: MsgRevoke["/cosmos.authz.v1beta1.MsgRevoke"] => MsgRevokeResponse
+    }
+
     fn_query! {
-        pub query_grantee_grants ["/cosmos.authz.v1beta1.Query/GranteeGrants"]: QueryGranteeGrantsRequest => QueryGranteeGrantsResponse
+        pub query_grantee_grants ["/cosmos.authz.v1beta1.Query/GranteeGrants"]: QueryGranteeGrantsRequest => QueryGranteeGrantsResponse,
+        all_pages: query_grantee_grants_all_pages => grants
     }
 
     fn_query! {
-        pub query_granter_grants ["/cosmos.authz.v1beta1.Query/GranterGrants"]: QueryGranterGrantsRequest => QueryGranterGrantsResponse
+        pub query_granter_grants ["/cosmos.authz.v1beta1.Query/GranterGrants"]: QueryGranterGrantsRequest => QueryGranterGrantsResponse,
+        all_pages: query_granter_grants_all_pages => grants
     }
 
     fn_query! {
-        pub query_grants ["/cosmos.authz.v1beta1.Query/Grants"]: QueryGrantsRequest => QueryGrantsResponse
+        pub query_grants ["/cosmos.authz.v1beta1.Query/Grants"]: QueryGrantsRequest => QueryGrantsResponse,
+        all_pages: query_grants_all_pages => grants
+    }
+
+    /// Grant `authorization` from `granter` to `grantee`, optionally until `expiration`.
+    pub fn grant_authorization(
+        &self,
+        granter: &SigningAccount,
+        grantee: &str,
+        authorization: Authorization,
+        expiration: Option<Timestamp>,
+    ) -> RunnerExecuteResult<MsgGrantResponse> {
+        self.grant(
+            MsgGrant {
+                granter: granter.address(),
+                grantee: grantee.to_string(),
+                grant: Some(Grant {
+                    authorization: Some(authorization.to_any()),
+                    expiration,
+                }),
+            },
+            granter,
+        )
+    }
+
+    /// Execute `msgs` on behalf of their signers, and return the response of each message.
+    /// Messages without a matching grant fail on chain like any other `MsgExec`.
+    pub fn exec_as(
+        &self,
+        grantee: &SigningAccount,
+        msgs: Vec<Any>,
+    ) -> RunnerResult<AuthzExecResponse> {
+        let res = self.exec(
+            MsgExec {
+                grantee: grantee.address(),
+                msgs,
+            },
+            grantee,
+        )?;
+
+        Ok(AuthzExecResponse {
+            results: res.data.results,
+            events: res.events,
+        })
+    }
+}
+
+/// Authorizations that can be granted with [`Authz::grant_authorization`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Authorization {
+    Generic(GenericAuthorization),
+    Send(SendAuthorization),
+    ContractExecution(ContractExecutionAuthorization),
+}
+
+impl Authorization {
+    /// Allow any message with the type url `msg`.
+    pub fn generic(msg: &str) -> Self {
+        Authorization::Generic(GenericAuthorization {
+            msg: msg.to_string(),
+        })
+    }
+
+    /// Allow bank sends of up to `spend_limit`.
+    pub fn send(spend_limit: &[Coin]) -> Self {
+        Authorization::Send(SendAuthorization {
+            spend_limit: spend_limit.iter().map(base_coin).collect(),
+            allow_list: vec![],
+        })
+    }
+
+    /// Allow `max_calls` executions of `contract`, with any message, sending up to
+    /// `funds_limit` in total when not empty.
+    pub fn contract_execution(contract: &str, max_calls: u64, funds_limit: &[Coin]) -> Self {
+        let limit = if funds_limit.is_empty() {
+            Any {
+                type_url: "/cosmwasm.wasm.v1.MaxCallsLimit".to_string(),
+                value: MaxCallsLimit {
+                    remaining: max_calls,
+                }
+                .encode_to_vec(),
+            }
+        } else {
+            Any {
+                type_url: "/cosmwasm.wasm.v1.CombinedLimit".to_string(),
+                value: CombinedLimit {
+                    calls_remaining: max_calls,
+                    amounts: funds_limit.iter().map(base_coin).collect(),
+                }
+                .encode_to_vec(),
+            }
+        };
+
+        Authorization::ContractExecution(ContractExecutionAuthorization {
+            grants: vec![ContractGrant {
+                contract: contract.to_string(),
+                limit: Some(limit),
+                filter: Some(Any {
+                    type_url: "/cosmwasm.wasm.v1.AllowAllMessagesFilter".to_string(),
+                    value: AllowAllMessagesFilter {}.encode_to_vec(),
+                }),
+            }],
+        })
+    }
+
+    pub fn to_any(&self) -> Any {
+        match self {
+            Authorization::Generic(authorization) => Any {
+                type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
+                value: authorization.encode_to_vec(),
+            },
+            Authorization::Send(authorization) => Any {
+                type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
+                value: authorization.encode_to_vec(),
+            },
+            Authorization::ContractExecution(authorization) => Any {
+                type_url: "/cosmwasm.wasm.v1.ContractExecutionAuthorization".to_string(),
+                value: authorization.encode_to_vec(),
+            },
+        }
+    }
+}
+
+fn base_coin(coin: &Coin) -> BaseCoin {
+    BaseCoin {
+        denom: coin.denom.clone(),
+        amount: coin.amount.to_string(),
+    }
+}
+
+/// Responses of the messages executed through [`Authz::exec_as`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthzExecResponse {
+    pub results: Vec<Vec<u8>>,
+    pub events: Vec<Event>,
+}
+
+impl AuthzExecResponse {
+    /// Decode the response of the message at `index`.
+    pub fn decode<R: Message + Default>(&self, index: usize) -> RunnerResult<R> {
+        let res = self
+            .results
+            .get(index)
+            .ok_or_else(|| RunnerError::GenericError(format!("no response at index {}", index)))?;
+
+        R::decode(res.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)
+            .map_err(RunnerError::DecodeError)
     }
 }
 
@@ -273,4 +438,133 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn authz_typed_grants() {
+        use cw1_whitelist::msg::InstantiateMsg;
+        use margined_neutron_std::types::cosmos::authz::v1beta1::{MsgRevoke, QueryGrantsRequest};
+        use margined_neutron_std::types::cosmos::bank::v1beta1::MsgSendResponse;
+        use margined_neutron_std::types::cosmwasm::wasm::v1::MsgExecuteContract;
+
+        use crate::{Authorization, Wasm};
+
+        let app = NeutronTestApp::new();
+        let authz = Authz::new(&app);
+        let wasm = Wasm::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (granter, grantee) = (&accs[0], &accs[1]);
+
+        let send = |amount: u128| Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: MsgSend {
+                from_address: granter.address(),
+                to_address: grantee.address(),
+                amount: vec![BaseCoin {
+                    denom: "untrn".to_string(),
+                    amount: amount.to_string(),
+                }],
+            }
+            .encode_to_vec(),
+        };
+
+        // messages without a grant are refused by the chain
+        let err = authz.exec_as(grantee, vec![send(1)]).unwrap_err();
+        assert!(err.to_string().contains("authorization not found"));
+
+        authz
+            .grant_authorization(
+                granter,
+                &grantee.address(),
+                Authorization::send(&[Coin::new(1_000u128, "untrn")]),
+                None,
+            )
+            .unwrap();
+
+        let res = authz.exec_as(grantee, vec![send(400), send(600)]).unwrap();
+        assert_eq!(res.results.len(), 2);
+        res.decode::<MsgSendResponse>(1).unwrap();
+        assert!(res.decode::<MsgSendResponse>(2).is_err());
+
+        // the spend limit is used up
+        assert!(authz.exec_as(grantee, vec![send(1)]).is_err());
+
+        // contract executions are limited in number of calls
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, granter)
+            .unwrap()
+            .data
+            .code_id;
+        let contract = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![granter.address()],
+                    mutable: false,
+                },
+                None,
+                Some("whitelist"),
+                &[],
+                granter,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        authz
+            .grant_authorization(
+                granter,
+                &grantee.address(),
+                Authorization::contract_execution(&contract, 1, &[]),
+                None,
+            )
+            .unwrap();
+
+        let execute = Any {
+            type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+            value: MsgExecuteContract {
+                sender: granter.address(),
+                contract: contract.clone(),
+                msg: br#"{"execute":{"msgs":[]}}"#.to_vec(),
+                funds: vec![],
+            }
+            .encode_to_vec(),
+        };
+        authz.exec_as(grantee, vec![execute.clone()]).unwrap();
+        assert!(authz.exec_as(grantee, vec![execute]).is_err());
+
+        // revoked grants are removed
+        authz
+            .grant_authorization(
+                granter,
+                &grantee.address(),
+                Authorization::generic("/neutron.dex.MsgDeposit"),
+                None,
+            )
+            .unwrap();
+        authz
+            .revoke(
+                MsgRevoke {
+                    granter: granter.address(),
+                    grantee: grantee.address(),
+                    msg_type_url: "/neutron.dex.MsgDeposit".to_string(),
+                },
+                granter,
+            )
+            .unwrap();
+        let err = authz
+            .query_grants(&QueryGrantsRequest {
+                granter: granter.address(),
+                grantee: grantee.address(),
+                msg_type_url: "/neutron.dex.MsgDeposit".to_string(),
+                pagination: None,
+            })
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("authorization not found for /neutron.dex.MsgDeposit type"));
+    }
 }
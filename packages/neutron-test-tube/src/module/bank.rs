@@ -1,12 +1,23 @@
+use margined_neutron_std::shim::Any;
 use margined_neutron_std::types::cosmos::bank::v1beta1::{
-    MsgSend, MsgSendResponse, QueryAllBalancesRequest, QueryAllBalancesResponse,
-    QueryBalanceRequest, QueryBalanceResponse, QueryTotalSupplyRequest, QueryTotalSupplyResponse,
+    Metadata, MsgMultiSend, MsgMultiSendResponse, MsgSend, MsgSendResponse, MsgSetSendEnabled,
+    QueryAllBalancesRequest, QueryAllBalancesResponse, QueryBalanceRequest, QueryBalanceResponse,
+    QueryDenomMetadataRequest, QueryDenomMetadataResponse, QueryDenomOwnersRequest,
+    QueryDenomOwnersResponse, QueryDenomsMetadataRequest, QueryDenomsMetadataResponse,
+    QueryParamsRequest, QueryParamsResponse, QuerySendEnabledRequest, QuerySendEnabledResponse,
+    QuerySpendableBalanceByDenomRequest, QuerySpendableBalanceByDenomResponse,
+    QuerySpendableBalancesRequest, QuerySpendableBalancesResponse, QuerySupplyOfRequest,
+    QuerySupplyOfResponse, QueryTotalSupplyRequest, QueryTotalSupplyResponse, SendEnabled,
 };
+use prost::Message;
+use test_tube_ntrn::runner::result::RunnerResult;
 use test_tube_ntrn::{fn_execute, fn_query};
 
 use test_tube_ntrn::module::Module;
 use test_tube_ntrn::runner::Runner;
 
+use crate::{Admin, NeutronTestApp};
+
 pub struct Bank<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -25,17 +36,85 @@ where
         pub send: MsgSend["/cosmos.bank.v1beta1.MsgSend"] => MsgSendResponse
     }
 
+    fn_execute! {
+        pub multi_send: MsgMultiSend["/cosmos.bank.v1beta1.MsgMultiSend"] => MsgMultiSendResponse
+    }
+
     fn_query! {
         pub query_balance ["/cosmos.bank.v1beta1.Query/Balance"]: QueryBalanceRequest => QueryBalanceResponse
     }
 
     fn_query! {
-        pub query_all_balances ["/cosmos.bank.v1beta1.Query/AllBalances"]: QueryAllBalancesRequest => QueryAllBalancesResponse
+        pub query_all_balances ["/cosmos.bank.v1beta1.Query/AllBalances"]: QueryAllBalancesRequest => QueryAllBalancesResponse,
+        all_pages: query_all_balances_all_pages => balances
+    }
+
+    fn_query! {
+        pub query_spendable_balances ["/cosmos.bank.v1beta1.Query/SpendableBalances"]: QuerySpendableBalancesRequest => QuerySpendableBalancesResponse,
+        all_pages: query_spendable_balances_all_pages => balances
+    }
+
+    fn_query! {
+        pub query_spendable_balance_by_denom ["/cosmos.bank.v1beta1.Query/SpendableBalanceByDenom"]: QuerySpendableBalanceByDenomRequest => QuerySpendableBalanceByDenomResponse
     }
 
     fn_query! {
         pub query_total_supply ["/cosmos.bank.v1beta1.Query/TotalSupply"]: QueryTotalSupplyRequest => QueryTotalSupplyResponse
     }
+
+    fn_query! {
+        pub query_supply_of ["/cosmos.bank.v1beta1.Query/SupplyOf"]: QuerySupplyOfRequest => QuerySupplyOfResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.bank.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_denom_metadata ["/cosmos.bank.v1beta1.Query/DenomMetadata"]: QueryDenomMetadataRequest => QueryDenomMetadataResponse
+    }
+
+    fn_query! {
+        pub query_denoms_metadata ["/cosmos.bank.v1beta1.Query/DenomsMetadata"]: QueryDenomsMetadataRequest => QueryDenomsMetadataResponse,
+        all_pages: query_denoms_metadata_all_pages => metadatas
+    }
+
+    fn_query! {
+        pub query_send_enabled ["/cosmos.bank.v1beta1.Query/SendEnabled"]: QuerySendEnabledRequest => QuerySendEnabledResponse
+    }
+
+    fn_query! {
+        pub query_denom_owners ["/cosmos.bank.v1beta1.Query/DenomOwners"]: QueryDenomOwnersRequest => QueryDenomOwnersResponse,
+        all_pages: query_denom_owners_all_pages => denom_owners
+    }
+}
+
+impl<'a> Bank<'a, NeutronTestApp> {
+    /// Set the metadata of any denom, e.g. the decimals of an `ibc/` denom.
+    pub fn set_denom_metadata(&self, metadata: &Metadata) -> RunnerResult<()> {
+        self.runner.set_denom_metadata(metadata)
+    }
+
+    /// Allow or forbid transfers of `denom`, signed by the bank authority.
+    pub fn set_send_enabled(&self, denom: &str, enabled: bool) -> RunnerResult<()> {
+        let admin = Admin::new(self.runner);
+
+        let msg = MsgSetSendEnabled {
+            authority: admin.authority()?,
+            send_enabled: vec![SendEnabled {
+                denom: denom.to_string(),
+                enabled,
+            }],
+            use_default_for: vec![],
+        };
+
+        admin.execute_as_admin(vec![Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSetSendEnabled".to_string(),
+            value: msg.encode_to_vec(),
+        }])?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -83,4 +162,187 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn bank_metadata_and_send_enabled() {
+        use margined_neutron_std::types::cosmos::bank::v1beta1::{
+            DenomUnit, Input, Metadata, MsgMultiSend, Output, QueryDenomMetadataRequest,
+            QueryDenomOwnersRequest, QuerySendEnabledRequest, QuerySupplyOfRequest,
+        };
+
+        let app = NeutronTestApp::new();
+        let bank = Bank::new(&app);
+
+        let accs = app
+            .init_accounts(
+                &[
+                    Coin::new(1_000_000_000_000u128, "untrn"),
+                    Coin::new(1_000u128, "uatom"),
+                ],
+                3,
+            )
+            .unwrap();
+
+        let uatom = |amount: u128| {
+            vec![BaseCoin {
+                denom: "uatom".to_string(),
+                amount: amount.to_string(),
+            }]
+        };
+        let send = |amount: u128| {
+            bank.send(
+                MsgSend {
+                    from_address: accs[0].address(),
+                    to_address: accs[1].address(),
+                    amount: uatom(amount),
+                },
+                &accs[0],
+            )
+        };
+
+        bank.multi_send(
+            MsgMultiSend {
+                inputs: vec![Input {
+                    address: accs[0].address(),
+                    coins: uatom(300),
+                }],
+                outputs: vec![
+                    Output {
+                        address: accs[1].address(),
+                        coins: uatom(100),
+                    },
+                    Output {
+                        address: accs[2].address(),
+                        coins: uatom(200),
+                    },
+                ],
+            },
+            &accs[0],
+        )
+        .unwrap();
+
+        let supply = bank
+            .query_supply_of(&QuerySupplyOfRequest {
+                denom: "uatom".to_string(),
+            })
+            .unwrap()
+            .amount
+            .unwrap();
+        assert_eq!(supply.amount, "3000");
+
+        let owners = bank
+            .query_denom_owners(&QueryDenomOwnersRequest {
+                denom: "uatom".to_string(),
+                pagination: None,
+            })
+            .unwrap()
+            .denom_owners;
+        assert_eq!(owners.len(), 3);
+
+        let metadata = Metadata {
+            description: "Cosmos Hub Atom".to_string(),
+            denom_units: vec![
+                DenomUnit {
+                    denom: "uatom".to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: "atom".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                },
+            ],
+            base: "uatom".to_string(),
+            display: "atom".to_string(),
+            name: "Cosmos Hub Atom".to_string(),
+            symbol: "ATOM".to_string(),
+            ..Default::default()
+        };
+        bank.set_denom_metadata(&metadata).unwrap();
+        assert_eq!(
+            bank.query_denom_metadata(&QueryDenomMetadataRequest {
+                denom: "uatom".to_string(),
+            })
+            .unwrap()
+            .metadata
+            .unwrap(),
+            metadata
+        );
+
+        // invalid metadata is refused
+        assert!(bank
+            .set_denom_metadata(&Metadata {
+                base: "uatom".to_string(),
+                ..Default::default()
+            })
+            .is_err());
+
+        // non-transferable tokens
+        bank.set_send_enabled("uatom", false).unwrap();
+        let send_enabled = bank
+            .query_send_enabled(&QuerySendEnabledRequest {
+                denoms: vec!["uatom".to_string()],
+                pagination: None,
+            })
+            .unwrap()
+            .send_enabled;
+        assert!(!send_enabled[0].enabled);
+        assert!(send(1).is_err());
+
+        bank.set_send_enabled("uatom", true).unwrap();
+        send(1).unwrap();
+    }
+
+    #[test]
+    fn bank_all_balances_pages() {
+        use margined_neutron_std::types::cosmos::bank::v1beta1::QueryAllBalancesRequest;
+        use test_tube_ntrn::{paginate, RunnerResult, PAGE_LIMIT};
+
+        let app = NeutronTestApp::new();
+        let bank = Bank::new(&app);
+
+        let coins: Vec<Coin> = (0..150)
+            .map(|i| Coin::new(1_000u128, format!("udenom{:03}", i)))
+            .chain([Coin::new(1_000_000_000u128, "untrn")])
+            .collect();
+        let acc = app.init_account(&coins).unwrap();
+
+        let request = QueryAllBalancesRequest {
+            address: acc.address(),
+            pagination: None,
+            resolve_denom: false,
+        };
+
+        // a single query stops at the default page limit
+        let first_page = bank.query_all_balances(&request).unwrap();
+        assert_eq!(first_page.balances.len() as u64, PAGE_LIMIT);
+        assert!(!first_page.pagination.unwrap().next_key.is_empty());
+
+        let balances = bank
+            .query_all_balances_all_pages(&request)
+            .unwrap()
+            .balances;
+        assert_eq!(balances.len(), 151);
+
+        let denoms = paginate(
+            |pagination| {
+                bank.query_all_balances(&QueryAllBalancesRequest {
+                    pagination: Some(pagination),
+                    ..request.clone()
+                })
+            },
+            |res| (res.balances, res.pagination),
+        )
+        .map(|coin| coin.map(|coin| coin.denom))
+        .collect::<RunnerResult<Vec<_>>>()
+        .unwrap();
+        assert_eq!(
+            denoms,
+            balances
+                .into_iter()
+                .map(|coin| coin.denom)
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,205 @@
+use margined_neutron_std::types::neutron::contractmanager::{
+    Failure, MsgResubmitFailure, MsgResubmitFailureResponse, QueryAddressFailureRequest,
+    QueryAddressFailureResponse, QueryFailuresRequest, QueryFailuresResponse, QueryParamsRequest,
+    QueryParamsResponse,
+};
+use serde_json::Value;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query, paginate};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+pub struct ContractManager<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for ContractManager<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> ContractManager<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub resubmit_failure: MsgResubmitFailure["/neutron.contractmanager.MsgResubmitFailure"] => MsgResubmitFailureResponse
+    }
+
+    fn_query! {
+        pub query_params ["/neutron.contractmanager.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_failures ["/neutron.contractmanager.Query/Failures"]: QueryFailuresRequest => QueryFailuresResponse
+    }
+
+    fn_query! {
+        pub query_address_failures ["/neutron.contractmanager.Query/AddressFailures"]: QueryFailuresRequest => QueryFailuresResponse
+    }
+
+    fn_query! {
+        pub query_address_failure ["/neutron.contractmanager.Query/AddressFailure"]: QueryAddressFailureRequest => QueryAddressFailureResponse
+    }
+
+    /// Assert that a `callback` sudo call (`response`, `error` or `timeout` of an IBC
+    /// packet) to `contract` failed and was recorded, and return the latest matching failure.
+    /// Every page of the contract's failures is read.
+    pub fn assert_failure(&self, contract: &str, callback: &str) -> RunnerResult<Failure> {
+        let mut latest = None;
+        for failure in paginate(
+            |pagination| {
+                self.query_address_failures(&QueryFailuresRequest {
+                    address: contract.to_string(),
+                    pagination: Some(pagination),
+                })
+            },
+            |res| (res.failures, res.pagination),
+        ) {
+            let failure = failure?;
+            let is_callback = serde_json::from_slice::<Value>(&failure.sudo_payload)
+                .map(|payload| payload.get(callback).is_some())
+                .unwrap_or(false);
+            if is_callback {
+                latest = Some(failure);
+            }
+        }
+
+        latest.ok_or_else(|| {
+            RunnerError::GenericError(format!(
+                "no failed `{}` callback recorded for {}",
+                callback, contract
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Coin};
+    use margined_neutron_std::types::neutron::contractmanager::{
+        MsgResubmitFailure, QueryFailuresRequest,
+    };
+    use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
+    use neutron_sdk::sudo::msg::{RequestPacketTimeoutHeight, SudoMsg};
+
+    use crate::sudo_recorder::SudoRecorder;
+    use crate::{Account, ContractManager, NeutronTestApp, PacketOutcome, Transfer};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn contract_manager_failures() {
+        let app = NeutronTestApp::new();
+        let contract_manager = ContractManager::new(&app);
+
+        let user = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let failures = contract_manager
+            .query_address_failures(&QueryFailuresRequest {
+                address: user.address(),
+                pagination: None,
+            })
+            .unwrap()
+            .failures;
+        assert!(failures.is_empty());
+
+        assert!(contract_manager
+            .resubmit_failure(
+                MsgResubmitFailure {
+                    sender: user.address(),
+                    failure_id: 0,
+                },
+                &user,
+            )
+            .is_err());
+
+        assert!(contract_manager
+            .assert_failure(&user.address(), "response")
+            .is_err());
+    }
+
+    #[test]
+    fn contract_manager_records_sudo_failure() {
+        let app = NeutronTestApp::new();
+        let contract_manager = ContractManager::new(&app);
+        let transfer = Transfer::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (owner, relayer) = (&accs[0], &accs[1]);
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+
+        let recorder = SudoRecorder::new(&app, &[Coin::new(10_000_000u128, "untrn")], owner);
+        let res = recorder
+            .dispatch(
+                vec![NeutronMsg::IbcTransfer {
+                    source_port: "transfer".to_string(),
+                    source_channel: channel_id,
+                    token: coin(10_000, "untrn"),
+                    sender: recorder.address.clone(),
+                    receiver: "cosmos1receiver".to_string(),
+                    timeout_height: RequestPacketTimeoutHeight {
+                        revision_number: None,
+                        revision_height: None,
+                    },
+                    timeout_timestamp: app.get_block_time_nanos() as u64 + 3_600_000_000_000,
+                    memo: String::new(),
+                    fee: IbcFee {
+                        recv_fee: vec![],
+                        ack_fee: vec![coin(1_000, "untrn")],
+                        timeout_fee: vec![coin(1_000, "untrn")],
+                    },
+                }
+                .into()],
+                owner,
+            )
+            .unwrap();
+
+        // the ack is processed even though the callback fails, and the failure is recorded
+        recorder.set_sudo_failure(true, owner);
+        transfer
+            .relay(
+                &res.events,
+                &PacketOutcome::transfer_success(),
+                &relayer.address(),
+            )
+            .unwrap();
+        assert!(recorder.sudo_calls().is_empty());
+
+        let failure = contract_manager
+            .assert_failure(&recorder.address, "response")
+            .unwrap();
+        assert_eq!(failure.address, recorder.address);
+        assert!(failure.error.contains("sudo failure"));
+        assert!(contract_manager
+            .assert_failure(&recorder.address, "timeout")
+            .is_err());
+
+        // once fixed, the contract resubmits the failure and receives the callback
+        recorder.set_sudo_failure(false, owner);
+        recorder
+            .dispatch(
+                vec![NeutronMsg::ResubmitFailure {
+                    failure_id: failure.id,
+                }
+                .into()],
+                owner,
+            )
+            .unwrap();
+        assert!(matches!(
+            recorder.sudo_calls()[..],
+            [SudoMsg::Response { .. }]
+        ));
+        assert!(contract_manager
+            .assert_failure(&recorder.address, "response")
+            .is_err());
+    }
+}
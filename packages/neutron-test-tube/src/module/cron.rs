@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::Event;
+use margined_neutron_std::shim::Any;
+use margined_neutron_std::types::neutron::cron::{
+    ExecutionStage, MsgAddSchedule, MsgExecuteContract, MsgRemoveSchedule, QueryGetScheduleRequest,
+    QueryGetScheduleResponse, QueryParamsRequest, QueryParamsResponse, QuerySchedulesRequest,
+    QuerySchedulesResponse, Schedule,
+};
+use prost::Message;
+use test_tube_ntrn::fn_query;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::{Admin, NeutronTestApp, BLOCK_TIME_SECONDS};
+
+pub struct Cron<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Cron<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Cron<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_query! {
+        pub query_params ["/neutron.cron.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_schedule ["/neutron.cron.Query/Schedule"]: QueryGetScheduleRequest => QueryGetScheduleResponse
+    }
+
+    fn_query! {
+        pub query_schedules ["/neutron.cron.Query/Schedules"]: QuerySchedulesRequest => QuerySchedulesResponse
+    }
+}
+
+impl<'a> Cron<'a, NeutronTestApp> {
+    /// Get a schedule by name.
+    pub fn schedule(&self, name: &str) -> RunnerResult<Schedule> {
+        self.query_schedule(&QueryGetScheduleRequest {
+            name: name.to_string(),
+        })?
+        .schedule
+        .ok_or_else(|| RunnerError::GenericError(format!("schedule `{}` not found", name)))
+    }
+
+    /// Add a schedule executing `msgs` every `period` blocks, signed by the cron authority.
+    pub fn add_schedule(
+        &self,
+        name: &str,
+        period: u64,
+        msgs: Vec<MsgExecuteContract>,
+        execution_stage: ExecutionStage,
+    ) -> RunnerResult<()> {
+        let admin = Admin::new(self.runner);
+
+        let msg = MsgAddSchedule {
+            authority: admin.authority()?,
+            name: name.to_string(),
+            period,
+            msgs,
+            execution_stage: execution_stage as i32,
+        };
+
+        admin.execute_as_admin(vec![Any {
+            type_url: "/neutron.cron.MsgAddSchedule".to_string(),
+            value: msg.encode_to_vec(),
+        }])?;
+
+        Ok(())
+    }
+
+    /// Remove a schedule, signed by the cron authority.
+    pub fn remove_schedule(&self, name: &str) -> RunnerResult<()> {
+        let admin = Admin::new(self.runner);
+
+        let msg = MsgRemoveSchedule {
+            authority: admin.authority()?,
+            name: name.to_string(),
+        };
+
+        admin.execute_as_admin(vec![Any {
+            type_url: "/neutron.cron.MsgRemoveSchedule".to_string(),
+            value: msg.encode_to_vec(),
+        }])?;
+
+        Ok(())
+    }
+
+    /// Produce blocks until the schedule `name` is executed, for at most `max_blocks` blocks.
+    ///
+    /// Returns the height of the execution and the `wasm` events emitted by the contracts
+    /// called by the schedule in that block.
+    pub fn wait_for_schedule(
+        &self,
+        name: &str,
+        max_blocks: u64,
+    ) -> RunnerResult<ScheduleExecution> {
+        let schedule = self.schedule(name)?;
+        let contracts: HashSet<String> = schedule.msgs.into_iter().map(|m| m.contract).collect();
+
+        for _ in 0..max_blocks {
+            let events = self.runner.advance_block(BLOCK_TIME_SECONDS)?;
+            let last_execute_height = self.schedule(name)?.last_execute_height;
+
+            if last_execute_height != schedule.last_execute_height {
+                let events = events
+                    .into_iter()
+                    .filter(|e| {
+                        e.ty.starts_with("wasm")
+                            && e.attributes.iter().any(|a| {
+                                a.key == "_contract_address" && contracts.contains(&a.value)
+                            })
+                    })
+                    .collect();
+
+                return Ok(ScheduleExecution {
+                    height: last_execute_height,
+                    events,
+                });
+            }
+        }
+
+        Err(RunnerError::GenericError(format!(
+            "schedule `{}` not executed within {} blocks",
+            name, max_blocks
+        )))
+    }
+}
+
+/// Execution of a cron schedule, see [`Cron::wait_for_schedule`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleExecution {
+    pub height: u64,
+    pub events: Vec<Event>,
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use cw1_whitelist::msg::InstantiateMsg;
+    use margined_neutron_std::types::neutron::cron::{
+        ExecutionStage, MsgExecuteContract, QuerySchedulesRequest,
+    };
+
+    use crate::{Account, Cron, NeutronTestApp, Wasm};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn cron_schedule_execution() {
+        let app = NeutronTestApp::new();
+        let wasm = Wasm::new(&app);
+        let cron = Cron::new(&app);
+
+        let owner = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &owner)
+            .unwrap()
+            .data
+            .code_id;
+
+        // cron executes contracts as the cron module account
+        let contract = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![app.get_module_address("cron").unwrap()],
+                    mutable: false,
+                },
+                None,
+                Some("keeper"),
+                &[],
+                &owner,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let period = 5;
+        cron.add_schedule(
+            "keeper",
+            period,
+            vec![MsgExecuteContract {
+                contract,
+                msg: r#"{"execute":{"msgs":[]}}"#.to_string(),
+            }],
+            ExecutionStage::EndBlocker,
+        )
+        .unwrap();
+
+        assert_eq!(cron.schedule("keeper").unwrap().period, period);
+
+        let first = cron.wait_for_schedule("keeper", 10).unwrap();
+        assert!(first.events.iter().any(|e| e
+            .attributes
+            .iter()
+            .any(|a| a.key == "action" && a.value == "execute")));
+
+        let second = cron.wait_for_schedule("keeper", 10).unwrap();
+        assert!(second.height - first.height >= period);
+        assert!(!second.events.is_empty());
+
+        cron.remove_schedule("keeper").unwrap();
+        let schedules = cron
+            .query_schedules(&QuerySchedulesRequest { pagination: None })
+            .unwrap()
+            .schedules;
+        assert!(schedules.is_empty());
+        assert!(cron.wait_for_schedule("keeper", 1).is_err());
+    }
+}
@@ -1,9 +1,20 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use cosmwasm_std::{Coin, Decimal, Timestamp, Uint128, Uint256, Uint512};
+use margined_neutron_std::shim::Timestamp as ProtoTimestamp;
 use margined_neutron_std::types::neutron::dex as DexTypes;
-use test_tube_ntrn::{fn_execute, fn_query};
+use margined_neutron_std::types::neutron::dex::tick_liquidity::Liquidity;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::{RunnerExecuteResult, RunnerResult};
+use test_tube_ntrn::{fn_execute, fn_query, Account, SigningAccount};
 
 use test_tube_ntrn::module::Module;
 use test_tube_ntrn::runner::Runner;
 
+use crate::{NeutronTestApp, BLOCK_TIME_SECONDS};
+
 pub struct Dex<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -27,7 +38,7 @@ where
     }
 
     fn_execute! {
-        pub multi_hop_swap: DexTypes::MsgMultiHopSwap["/neutron.dex.MsgDeposit"] => DexTypes::MsgMultiHopSwapResponse
+        pub multi_hop_swap: DexTypes::MsgMultiHopSwap["/neutron.dex.MsgMultiHopSwap"] => DexTypes::MsgMultiHopSwapResponse
     }
 
     fn_execute! {
@@ -51,11 +62,13 @@ where
     }
 
     fn_query! {
-        pub limit_order_tranche_user_all ["/neutron.dex.Query/LimitOrderTrancheUserAll"]: DexTypes::QueryAllLimitOrderTrancheUserRequest => DexTypes::QueryAllLimitOrderTrancheUserResponse
+        pub limit_order_tranche_user_all ["/neutron.dex.Query/LimitOrderTrancheUserAll"]: DexTypes::QueryAllLimitOrderTrancheUserRequest => DexTypes::QueryAllLimitOrderTrancheUserResponse,
+        all_pages: limit_order_tranche_user_all_pages => limit_order_tranche_user
     }
 
     fn_query! {
-        pub limit_order_tranche_user_all_by_address ["/neutron.dex.Query/LimitOrderTrancheUserAllByAddress"]: DexTypes::QueryAllLimitOrderTrancheUserByAddressRequest => DexTypes::QueryAllLimitOrderTrancheUserByAddressResponse
+        pub limit_order_tranche_user_all_by_address ["/neutron.dex.Query/LimitOrderTrancheUserAllByAddress"]: DexTypes::QueryAllLimitOrderTrancheUserByAddressRequest => DexTypes::QueryAllLimitOrderTrancheUserByAddressResponse,
+        all_pages: limit_order_tranche_user_all_by_address_all_pages => limit_orders
     }
 
     fn_query! {
@@ -63,15 +76,18 @@ where
     }
 
     fn_query! {
-        pub limit_order_tranche_all ["/neutron.dex.Query/LimitOrderTrancheAll"]: DexTypes::QueryAllLimitOrderTrancheRequest => DexTypes::QueryAllLimitOrderTrancheResponse
+        pub limit_order_tranche_all ["/neutron.dex.Query/LimitOrderTrancheAll"]: DexTypes::QueryAllLimitOrderTrancheRequest => DexTypes::QueryAllLimitOrderTrancheResponse,
+        all_pages: limit_order_tranche_all_pages => limit_order_tranche
     }
 
     fn_query! {
-        pub user_deposits_all ["/neutron.dex.Query/UserDepositsAll"]: DexTypes::QueryAllUserDepositsRequest => DexTypes::QueryAllUserDepositsResponse
+        pub user_deposits_all ["/neutron.dex.Query/UserDepositsAll"]: DexTypes::QueryAllUserDepositsRequest => DexTypes::QueryAllUserDepositsResponse,
+        all_pages: user_deposits_all_pages => deposits
     }
 
     fn_query! {
-        pub tick_liquidity_all ["/neutron.dex.Query/TickLiquidityAll"]: DexTypes::QueryAllTickLiquidityRequest => DexTypes::QueryAllTickLiquidityResponse
+        pub tick_liquidity_all ["/neutron.dex.Query/TickLiquidityAll"]: DexTypes::QueryAllTickLiquidityRequest => DexTypes::QueryAllTickLiquidityResponse,
+        all_pages: tick_liquidity_all_pages => tick_liquidity
     }
 
     fn_query! {
@@ -79,11 +95,13 @@ where
     }
 
     fn_query! {
-        pub inactive_limit_order_tranche_all ["/neutron.dex.Query/AllInactiveLimitOrderTranche"]: DexTypes::QueryAllInactiveLimitOrderTrancheRequest => DexTypes::QueryAllInactiveLimitOrderTrancheResponse
+        pub inactive_limit_order_tranche_all ["/neutron.dex.Query/AllInactiveLimitOrderTranche"]: DexTypes::QueryAllInactiveLimitOrderTrancheRequest => DexTypes::QueryAllInactiveLimitOrderTrancheResponse,
+        all_pages: inactive_limit_order_tranche_all_pages => inactive_limit_order_tranche
     }
 
     fn_query! {
-        pub pool_reserves_all ["/neutron.dex.Query/AllPoolReserves"]: DexTypes::QueryAllPoolReservesRequest => DexTypes::QueryAllPoolReservesResponse
+        pub pool_reserves_all ["/neutron.dex.Query/AllPoolReserves"]: DexTypes::QueryAllPoolReservesRequest => DexTypes::QueryAllPoolReservesResponse,
+        all_pages: pool_reserves_all_pages => pool_reserves
     }
 
     fn_query! {
@@ -111,7 +129,717 @@ where
     }
 
     fn_query! {
-        pub pool_metadata_all ["/neutron.dex.Query/AllPoolMetadata"]: DexTypes::QueryAllPoolMetadataRequest => DexTypes::QueryAllPoolMetadataResponse
+        pub pool_metadata_all ["/neutron.dex.Query/AllPoolMetadata"]: DexTypes::QueryAllPoolMetadataRequest => DexTypes::QueryAllPoolMetadataResponse,
+        all_pages: pool_metadata_all_pages => pool_metadata
+    }
+
+    /// Deposit `amounts[i]` of `(token_a, token_b)` around `tick_index` in the pool of fee
+    /// `fee_tiers[i]`, for each fee tier.
+    pub fn provide_liquidity(
+        &self,
+        pair: (&str, &str),
+        tick_index: i64,
+        fee_tiers: &[u64],
+        amounts: &[(Uint128, Uint128)],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<DexTypes::MsgDepositResponse> {
+        if fee_tiers.len() != amounts.len() {
+            return Err(RunnerError::GenericError(format!(
+                "got {} fee tiers for {} amounts",
+                fee_tiers.len(),
+                amounts.len()
+            )));
+        }
+
+        let (token_a, token_b) = pair;
+        self.deposit(
+            DexTypes::MsgDeposit {
+                creator: signer.address(),
+                receiver: signer.address(),
+                token_a: token_a.to_string(),
+                token_b: token_b.to_string(),
+                amounts_a: amounts.iter().map(|(a, _)| a.to_string()).collect(),
+                amounts_b: amounts.iter().map(|(_, b)| b.to_string()).collect(),
+                tick_indexes_a_to_b: vec![tick_index; fee_tiers.len()],
+                fees: fee_tiers.to_vec(),
+                options: vec![DexTypes::DepositOptions::default(); fee_tiers.len()],
+            },
+            signer,
+        )
+    }
+
+    /// Place `order` with `signer` as creator.
+    pub fn submit_limit_order(
+        &self,
+        order: &LimitOrder,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<DexTypes::MsgPlaceLimitOrderResponse> {
+        self.place_limit_order(order.to_msg(&signer.address()), signer)
+    }
+
+    /// Liquidity of both sides of the `token_a`/`token_b` pair: pool reserves from
+    /// `pool_reserves_all` and limit orders from `tick_liquidity_all`.
+    pub fn order_book(&self, token_a: &str, token_b: &str) -> RunnerResult<OrderBookSnapshot> {
+        let pair_id = pair_id(token_a, token_b);
+        let (token0, token1) = pair_id.split_once("<>").unwrap();
+        let (token0, token1) = (
+            self.order_book_side(&pair_id, token0)?,
+            self.order_book_side(&pair_id, token1)?,
+        );
+
+        Ok(OrderBookSnapshot {
+            pair_id,
+            token0,
+            token1,
+        })
+    }
+
+    fn order_book_side(&self, pair_id: &str, maker_denom: &str) -> RunnerResult<OrderBookSide> {
+        let pools = self
+            .pool_reserves_all_pages(&DexTypes::QueryAllPoolReservesRequest {
+                pair_id: pair_id.to_string(),
+                token_in: maker_denom.to_string(),
+                pagination: None,
+            })?
+            .pool_reserves
+            .into_iter()
+            .filter_map(|pool| {
+                let key = pool.key?;
+                Some(
+                    parse_reserves(&pool.reserves_maker_denom).and_then(|reserves| {
+                        Ok(PoolLiquidity {
+                            tick_index: key.tick_index_taker_to_maker,
+                            fee: key.fee,
+                            price: parse_price(&pool.price_taker_to_maker)?,
+                            reserves,
+                        })
+                    }),
+                )
+            })
+            .collect::<RunnerResult<Vec<_>>>()?;
+
+        let limit_orders = self
+            .tick_liquidity_all_pages(&DexTypes::QueryAllTickLiquidityRequest {
+                pair_id: pair_id.to_string(),
+                token_in: maker_denom.to_string(),
+                pagination: None,
+            })?
+            .tick_liquidity
+            .into_iter()
+            .filter_map(|tick| match tick.liquidity? {
+                Liquidity::LimitOrderTranche(tranche) => {
+                    let key = tranche.key?;
+                    Some(
+                        parse_reserves(&tranche.reserves_maker_denom).and_then(|reserves| {
+                            Ok(TrancheLiquidity {
+                                tick_index: key.tick_index_taker_to_maker,
+                                tranche_key: key.tranche_key,
+                                price: parse_price(&tranche.price_taker_to_maker)?,
+                                reserves,
+                            })
+                        }),
+                    )
+                }
+                Liquidity::PoolReserves(_) => None,
+            })
+            .collect::<RunnerResult<Vec<_>>>()?;
+
+        Ok(OrderBookSide {
+            maker_denom: maker_denom.to_string(),
+            pools,
+            limit_orders,
+        })
+    }
+}
+
+impl<'a> Dex<'a, NeutronTestApp> {
+    /// Place a `GOOD_TIL_TIME` or `JUST_IN_TIME` order and advance blocks until the dex end
+    /// blocker purges it, jumping straight to the expiration time of `GOOD_TIL_TIME` orders.
+    pub fn place_and_expire(
+        &self,
+        order: &LimitOrder,
+        signer: &SigningAccount,
+        max_blocks: u64,
+    ) -> RunnerResult<ExpiredLimitOrder> {
+        if !matches!(
+            order.order_type,
+            DexTypes::LimitOrderType::GoodTilTime | DexTypes::LimitOrderType::JustInTime
+        ) {
+            return Err(RunnerError::GenericError(format!(
+                "{} orders do not expire",
+                order.order_type.as_str_name()
+            )));
+        }
+
+        let tranche_key = self.submit_limit_order(order, signer)?.data.tranche_key;
+
+        if let Some(expiration_time) = order.expiration_time {
+            let now = self.runner.get_block_timestamp();
+            if expiration_time > now.plus_seconds(BLOCK_TIME_SECONDS) {
+                self.runner
+                    .advance_block(expiration_time.seconds() - now.seconds())?;
+            }
+        }
+
+        self.wait_for_expiry(&tranche_key, &signer.address(), max_blocks)
+    }
+
+    /// Advance blocks until the tranche `tranche_key` is purged, and return what `owner` can
+    /// withdraw from it.
+    pub fn wait_for_expiry(
+        &self,
+        tranche_key: &str,
+        owner: &str,
+        max_blocks: u64,
+    ) -> RunnerResult<ExpiredLimitOrder> {
+        for i in 0..=max_blocks {
+            if let Some(tranche) = self.inactive_tranche(tranche_key)? {
+                return ExpiredLimitOrder::new(
+                    tranche,
+                    self.tranche_user(tranche_key, owner)?,
+                    self.runner.get_block_height(),
+                );
+            }
+
+            if i < max_blocks {
+                self.runner.advance_block(BLOCK_TIME_SECONDS)?;
+            }
+        }
+
+        Err(RunnerError::GenericError(format!(
+            "limit order tranche `{}` not purged within {} blocks",
+            tranche_key, max_blocks
+        )))
+    }
+
+    /// Inactive tranche `tranche_key`, if it has expired.
+    pub fn inactive_tranche(
+        &self,
+        tranche_key: &str,
+    ) -> RunnerResult<Option<DexTypes::LimitOrderTranche>> {
+        Ok(self
+            .inactive_limit_order_tranche_all_pages(
+                &DexTypes::QueryAllInactiveLimitOrderTrancheRequest { pagination: None },
+            )?
+            .inactive_limit_order_tranche
+            .into_iter()
+            .find(|tranche| {
+                tranche
+                    .key
+                    .as_ref()
+                    .is_some_and(|key| key.tranche_key == tranche_key)
+            }))
+    }
+
+    fn tranche_user(
+        &self,
+        tranche_key: &str,
+        owner: &str,
+    ) -> RunnerResult<DexTypes::LimitOrderTrancheUser> {
+        self.limit_order_tranche_user(&DexTypes::QueryGetLimitOrderTrancheUserRequest {
+            address: owner.to_string(),
+            tranche_key: tranche_key.to_string(),
+            ..Default::default()
+        })?
+        .limit_order_tranche_user
+        .ok_or_else(|| {
+            RunnerError::GenericError(format!(
+                "{} has no share of tranche `{}`",
+                owner, tranche_key
+            ))
+        })
+    }
+}
+
+/// Largest tick index accepted by the dex, in absolute value.
+pub const MAX_TICK_INDEX: i64 = 559_680;
+
+/// Id of the `token_a`/`token_b` pair, with the denoms sorted as the dex does.
+pub fn pair_id(token_a: &str, token_b: &str) -> String {
+    if token_a <= token_b {
+        format!("{}<>{}", token_a, token_b)
+    } else {
+        format!("{}<>{}", token_b, token_a)
+    }
+}
+
+/// Price at `tick_index`, `1.0001^-tick_index`, computed as the dex `CalcPrice` does: `1.0001`
+/// raised to `|tick_index|` by squaring, each product rounded to the 27 decimals of `PrecDec`,
+/// then inverted for positive ticks. The result is exactly the price of the chain.
+pub fn price_from_tick(tick_index: i64) -> RunnerResult<PrecDec> {
+    if tick_index.unsigned_abs() > MAX_TICK_INDEX as u64 {
+        return Err(RunnerError::GenericError(format!(
+            "tick index {} out of range",
+            tick_index
+        )));
+    }
+
+    let one = Uint512::from(10u8).pow(PrecDec::DECIMAL_PLACES);
+    let power = prec_dec_pow(
+        one * Uint512::from(10_001u16) / Uint512::from(10_000u16),
+        tick_index.unsigned_abs(),
+    );
+    let atomics = if tick_index > 0 {
+        // `PrecDec::Quo`: truncated at twice the precision, then rounded
+        chop_precision_and_round(one * one * one / power)
+    } else {
+        power
+    };
+
+    Ok(PrecDec(Uint256::try_from(atomics).unwrap()))
+}
+
+/// Tick index whose price is the closest to `price`.
+pub fn tick_from_price(price: PrecDec) -> RunnerResult<i64> {
+    let (min, max) = (
+        price_from_tick(MAX_TICK_INDEX)?,
+        price_from_tick(-MAX_TICK_INDEX)?,
+    );
+    if price < min || price > max {
+        return Err(RunnerError::GenericError(format!(
+            "price {} out of range",
+            price
+        )));
+    }
+
+    // prices decrease with the tick index: find the first tick priced at most `price`
+    let (mut low, mut high) = (-MAX_TICK_INDEX, MAX_TICK_INDEX);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if price_from_tick(mid)? <= price {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    if low > -MAX_TICK_INDEX {
+        let above = price_from_tick(low - 1)?;
+        if above.0 - price.0 < price.0 - price_from_tick(low)?.0 {
+            return Ok(low - 1);
+        }
+    }
+
+    Ok(low)
+}
+
+/// `PrecDec::Power` on the atomics of `base`, with the same order of products.
+fn prec_dec_pow(mut base: Uint512, mut exp: u64) -> Uint512 {
+    let one = Uint512::from(10u8).pow(PrecDec::DECIMAL_PLACES);
+    if exp == 0 {
+        return one;
+    }
+
+    let mut tmp = one;
+    while exp > 1 {
+        if exp % 2 != 0 {
+            tmp = chop_precision_and_round(tmp * base);
+        }
+        exp /= 2;
+        base = chop_precision_and_round(base * base);
+    }
+
+    chop_precision_and_round(base * tmp)
+}
+
+/// Drops the extra 27 decimals of a product of atomics, rounding half to even.
+fn chop_precision_and_round(value: Uint512) -> Uint512 {
+    let one = Uint512::from(10u8).pow(PrecDec::DECIMAL_PLACES);
+    let (quotient, remainder) = (value / one, value % one);
+    let half = one / Uint512::from(2u8);
+    if remainder > half || (remainder == half && quotient % Uint512::from(2u8) == Uint512::one()) {
+        quotient + Uint512::one()
+    } else {
+        quotient
+    }
+}
+
+fn parse_price(price: &str) -> RunnerResult<PrecDec> {
+    if price.is_empty() {
+        return Ok(PrecDec::default());
+    }
+
+    PrecDec::from_proto(price)
+}
+
+fn parse_reserves(amount: &str) -> RunnerResult<Uint128> {
+    if amount.is_empty() {
+        return Ok(Uint128::zero());
+    }
+
+    Uint128::from_str(amount).map_err(|e| RunnerError::GenericError(e.to_string()))
+}
+
+/// Fixed point decimal with the 27 decimal places of the dex `PrecDec`.
+///
+/// Dex messages and queries carry it as its atomics, see [`PrecDec::to_proto`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrecDec(Uint256);
+
+impl PrecDec {
+    pub const DECIMAL_PLACES: u32 = 27;
+
+    pub fn from_atomics(atomics: impl Into<Uint256>) -> Self {
+        Self(atomics.into())
+    }
+
+    /// Parse the proto representation of a `PrecDec`.
+    pub fn from_proto(atomics: &str) -> RunnerResult<Self> {
+        Uint256::from_str(atomics)
+            .map(Self)
+            .map_err(|e| RunnerError::GenericError(e.to_string()))
+    }
+
+    pub fn one() -> Self {
+        Self(Uint256::from(10u8).pow(Self::DECIMAL_PLACES))
+    }
+
+    pub fn atomics(&self) -> Uint256 {
+        self.0
+    }
+
+    /// Proto representation, as used in `limit_sell_price` for example.
+    pub fn to_proto(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl From<Decimal> for PrecDec {
+    fn from(value: Decimal) -> Self {
+        let factor = 10u128.pow(Self::DECIMAL_PLACES - Decimal::DECIMAL_PLACES);
+        Self(Uint256::from(value.atomics()) * Uint256::from(factor))
+    }
+}
+
+impl FromStr for PrecDec {
+    type Err = RunnerError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || RunnerError::GenericError(format!("invalid PrecDec `{}`", input));
+
+        let (whole, fractional) = input.split_once('.').unwrap_or((input, ""));
+        if fractional.len() > Self::DECIMAL_PLACES as usize {
+            return Err(invalid());
+        }
+
+        let digits = format!(
+            "{}{:0<width$}",
+            whole,
+            fractional,
+            width = Self::DECIMAL_PLACES as usize
+        );
+        Uint256::from_str(&digits).map(Self).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for PrecDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let one = Self::one().0;
+        let (whole, fractional) = (self.0 / one, self.0 % one);
+
+        if fractional.is_zero() {
+            return write!(f, "{}", whole);
+        }
+
+        let fractional = format!(
+            "{:0>width$}",
+            fractional.to_string(),
+            width = Self::DECIMAL_PLACES as usize
+        );
+        write!(f, "{}.{}", whole, fractional.trim_end_matches('0'))
+    }
+}
+
+/// Builder of `MsgPlaceLimitOrder`, with one constructor per order type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitOrder {
+    pub token_in: String,
+    pub token_out: String,
+    pub amount_in: Uint128,
+    pub limit_sell_price: PrecDec,
+    pub order_type: DexTypes::LimitOrderType,
+    pub expiration_time: Option<Timestamp>,
+    pub max_amount_out: Option<Uint128>,
+    pub min_average_sell_price: Option<PrecDec>,
+    pub receiver: Option<String>,
+}
+
+impl LimitOrder {
+    fn new(
+        order_type: DexTypes::LimitOrderType,
+        token_in: &str,
+        token_out: &str,
+        amount_in: impl Into<Uint128>,
+        limit_sell_price: PrecDec,
+    ) -> Self {
+        Self {
+            token_in: token_in.to_string(),
+            token_out: token_out.to_string(),
+            amount_in: amount_in.into(),
+            limit_sell_price,
+            order_type,
+            expiration_time: None,
+            max_amount_out: None,
+            min_average_sell_price: None,
+            receiver: None,
+        }
+    }
+
+    /// Rests on the book until filled or cancelled.
+    pub fn good_til_cancelled(
+        token_in: &str,
+        token_out: &str,
+        amount_in: impl Into<Uint128>,
+        limit_sell_price: PrecDec,
+    ) -> Self {
+        Self::new(
+            DexTypes::LimitOrderType::GoodTilCancelled,
+            token_in,
+            token_out,
+            amount_in,
+            limit_sell_price,
+        )
+    }
+
+    /// Filled entirely against the book or not at all.
+    pub fn fill_or_kill(
+        token_in: &str,
+        token_out: &str,
+        amount_in: impl Into<Uint128>,
+        limit_sell_price: PrecDec,
+    ) -> Self {
+        Self::new(
+            DexTypes::LimitOrderType::FillOrKill,
+            token_in,
+            token_out,
+            amount_in,
+            limit_sell_price,
+        )
+    }
+
+    /// Filled as much as possible against the book, the rest is refunded.
+    pub fn immediate_or_cancel(
+        token_in: &str,
+        token_out: &str,
+        amount_in: impl Into<Uint128>,
+        limit_sell_price: PrecDec,
+    ) -> Self {
+        Self::new(
+            DexTypes::LimitOrderType::ImmediateOrCancel,
+            token_in,
+            token_out,
+            amount_in,
+            limit_sell_price,
+        )
+    }
+
+    /// Rests on the book until the end of the block.
+    pub fn just_in_time(
+        token_in: &str,
+        token_out: &str,
+        amount_in: impl Into<Uint128>,
+        limit_sell_price: PrecDec,
+    ) -> Self {
+        Self::new(
+            DexTypes::LimitOrderType::JustInTime,
+            token_in,
+            token_out,
+            amount_in,
+            limit_sell_price,
+        )
+    }
+
+    /// Rests on the book until `expiration_time`.
+    pub fn good_til_time(
+        token_in: &str,
+        token_out: &str,
+        amount_in: impl Into<Uint128>,
+        limit_sell_price: PrecDec,
+        expiration_time: Timestamp,
+    ) -> Self {
+        Self {
+            expiration_time: Some(expiration_time),
+            ..Self::new(
+                DexTypes::LimitOrderType::GoodTilTime,
+                token_in,
+                token_out,
+                amount_in,
+                limit_sell_price,
+            )
+        }
+    }
+
+    pub fn with_receiver(self, receiver: &str) -> Self {
+        Self {
+            receiver: Some(receiver.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_max_amount_out(self, max_amount_out: impl Into<Uint128>) -> Self {
+        Self {
+            max_amount_out: Some(max_amount_out.into()),
+            ..self
+        }
+    }
+
+    pub fn with_min_average_sell_price(self, min_average_sell_price: PrecDec) -> Self {
+        Self {
+            min_average_sell_price: Some(min_average_sell_price),
+            ..self
+        }
+    }
+
+    /// The order as a message created by `creator`, who also receives the proceeds unless a
+    /// receiver is set.
+    #[allow(deprecated)]
+    pub fn to_msg(&self, creator: &str) -> DexTypes::MsgPlaceLimitOrder {
+        DexTypes::MsgPlaceLimitOrder {
+            creator: creator.to_string(),
+            receiver: self.receiver.clone().unwrap_or_else(|| creator.to_string()),
+            token_in: self.token_in.clone(),
+            token_out: self.token_out.clone(),
+            tick_index_in_to_out: 0,
+            amount_in: self.amount_in.to_string(),
+            order_type: self.order_type as i32,
+            expiration_time: self.expiration_time.map(|time| ProtoTimestamp {
+                seconds: time.seconds() as i64,
+                nanos: time.subsec_nanos() as i32,
+            }),
+            max_amount_out: self
+                .max_amount_out
+                .map(|amount| amount.to_string())
+                .unwrap_or_default(),
+            limit_sell_price: self.limit_sell_price.to_proto(),
+            min_average_sell_price: self
+                .min_average_sell_price
+                .map(|price| price.to_proto())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Liquidity of a pair, by maker denom, at the time of the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBookSnapshot {
+    pub pair_id: String,
+    /// Liquidity selling the first denom of the pair
+    pub token0: OrderBookSide,
+    /// Liquidity selling the second denom of the pair
+    pub token1: OrderBookSide,
+}
+
+impl OrderBookSnapshot {
+    /// Side of the book selling `maker_denom`.
+    pub fn side(&self, maker_denom: &str) -> Option<&OrderBookSide> {
+        [&self.token0, &self.token1]
+            .into_iter()
+            .find(|side| side.maker_denom == maker_denom)
+    }
+}
+
+/// Liquidity selling `maker_denom`, with tick indexes taker to maker.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OrderBookSide {
+    pub maker_denom: String,
+    pub pools: Vec<PoolLiquidity>,
+    pub limit_orders: Vec<TrancheLiquidity>,
+}
+
+impl OrderBookSide {
+    /// Pool and limit order reserves aggregated by tick index.
+    pub fn levels(&self) -> BTreeMap<i64, Uint128> {
+        let mut levels = BTreeMap::new();
+        let reserves = self
+            .pools
+            .iter()
+            .map(|pool| (pool.tick_index, pool.reserves))
+            .chain(
+                self.limit_orders
+                    .iter()
+                    .map(|tranche| (tranche.tick_index, tranche.reserves)),
+            );
+
+        for (tick_index, amount) in reserves {
+            *levels.entry(tick_index).or_insert_with(Uint128::zero) += amount;
+        }
+
+        levels
+    }
+
+    pub fn total_reserves(&self) -> Uint128 {
+        self.levels().values().copied().sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolLiquidity {
+    pub tick_index: i64,
+    pub fee: u64,
+    /// Taker to maker price computed by the chain, `price_from_tick(tick_index)`
+    pub price: PrecDec,
+    pub reserves: Uint128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrancheLiquidity {
+    pub tick_index: i64,
+    pub tranche_key: String,
+    /// Taker to maker price computed by the chain, `price_from_tick(tick_index)`
+    pub price: PrecDec,
+    pub reserves: Uint128,
+}
+
+/// A purged `GOOD_TIL_TIME` or `JUST_IN_TIME` order and what its owner can withdraw from it
+/// with `withdraw_filled_limit_order`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiredLimitOrder {
+    pub tranche: DexTypes::LimitOrderTranche,
+    /// Height at which the expiry was observed
+    pub height: i64,
+    /// Unfilled part of the order, in the maker denom
+    pub unfilled: Coin,
+    /// Proceeds of the filled part, in the taker denom
+    pub filled: Coin,
+}
+
+impl ExpiredLimitOrder {
+    /// Shares are minted one for one with the maker denom deposited in the tranche, so the
+    /// owner gets the pro rata share of the remaining reserves of the tranche.
+    fn new(
+        tranche: DexTypes::LimitOrderTranche,
+        user: DexTypes::LimitOrderTrancheUser,
+        height: i64,
+    ) -> RunnerResult<Self> {
+        let trade_pair_id = tranche
+            .key
+            .as_ref()
+            .and_then(|key| key.trade_pair_id.clone())
+            .ok_or_else(|| RunnerError::GenericError("tranche without key".to_string()))?;
+
+        let total = parse_reserves(&tranche.total_maker_denom)?;
+        let shares = parse_reserves(&user.shares_owned)?
+            .checked_sub(parse_reserves(&user.shares_withdrawn)?)
+            .unwrap_or_default();
+        let pro_rata = |reserves: &str| -> RunnerResult<Uint128> {
+            if total.is_zero() {
+                return Ok(Uint128::zero());
+            }
+            Ok(parse_reserves(reserves)?.multiply_ratio(shares, total))
+        };
+
+        Ok(Self {
+            unfilled: Coin::new(
+                pro_rata(&tranche.reserves_maker_denom)?,
+                trade_pair_id.maker_denom,
+            ),
+            filled: Coin::new(
+                pro_rata(&tranche.reserves_taker_denom)?,
+                trade_pair_id.taker_denom,
+            ),
+            tranche,
+            height,
+        })
     }
 }
 
@@ -188,4 +916,306 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn dex_tick_math() {
+        use std::str::FromStr;
+
+        use crate::{price_from_tick, tick_from_price, PrecDec, MAX_TICK_INDEX};
+
+        assert_eq!(price_from_tick(0).unwrap(), PrecDec::one());
+        assert_eq!(
+            price_from_tick(1).unwrap().to_string(),
+            "0.999900009999000099990001"
+        );
+        assert_eq!(
+            price_from_tick(-1).unwrap(),
+            PrecDec::from_str("1.0001").unwrap()
+        );
+        assert!(price_from_tick(MAX_TICK_INDEX + 1).is_err());
+
+        // the limit price of the order placed in `dex_integration`
+        let price = PrecDec::from_proto("10000000000000000000").unwrap();
+        assert_eq!(price.to_string(), "0.00000001");
+        assert_eq!(tick_from_price(price).unwrap(), 184216);
+
+        for tick in [-MAX_TICK_INDEX, -184216, -1, 0, 1, 42, 100_000] {
+            assert_eq!(
+                tick_from_price(price_from_tick(tick).unwrap()).unwrap(),
+                tick
+            );
+        }
+    }
+
+    #[test]
+    fn dex_liquidity_and_order_book() {
+        use cosmwasm_std::Uint128;
+
+        use crate::{price_from_tick, LimitOrder, MAX_TICK_INDEX};
+
+        let app = NeutronTestApp::new();
+        let signer = app
+            .init_account(&[
+                Coin::new(1_000_000_000_000_000_000_000_000u128, "untrn"),
+                Coin::new(1_000_000_000_000u128, "usdc"),
+            ])
+            .unwrap();
+        let dex = Dex::new(&app);
+
+        // single sided liquidity, so that orders selling usdc never cross the book
+        dex.provide_liquidity(
+            ("usdc", "untrn"),
+            0,
+            &[1, 5],
+            &[
+                (Uint128::new(1_000_000), Uint128::zero()),
+                (Uint128::new(2_000_000), Uint128::zero()),
+            ],
+            &signer,
+        )
+        .unwrap();
+
+        assert!(dex
+            .provide_liquidity(("usdc", "untrn"), 0, &[1], &[], &signer)
+            .is_err());
+
+        dex.submit_limit_order(
+            &LimitOrder::good_til_cancelled(
+                "usdc",
+                "untrn",
+                1_000_000u128,
+                price_from_tick(10).unwrap(),
+            ),
+            &signer,
+        )
+        .unwrap();
+
+        let book = dex.order_book("usdc", "untrn").unwrap();
+        assert_eq!(book.pair_id, "untrn<>usdc");
+        assert_eq!(book.token0.maker_denom, "untrn");
+        assert_eq!(book.token1.maker_denom, "usdc");
+
+        let usdc = book.side("usdc").unwrap();
+        assert_eq!(usdc.pools.len(), 2);
+        assert_eq!(usdc.limit_orders.len(), 1);
+        assert_eq!(usdc.limit_orders[0].reserves, Uint128::new(1_000_000));
+        assert_eq!(usdc.total_reserves(), Uint128::new(4_000_000));
+
+        let untrn = book.side("untrn").unwrap();
+        assert!(untrn.limit_orders.is_empty());
+        assert!(untrn.total_reserves().is_zero());
+
+        // the limit sell price is the inverse of the taker to maker price of the tranche,
+        // so an order at `price_from_tick(t)` rests at tick `-t`
+        for tick in [-100_000, 100_000] {
+            dex.submit_limit_order(
+                &LimitOrder::good_til_cancelled(
+                    "usdc",
+                    "untrn",
+                    1_000_000u128,
+                    price_from_tick(tick).unwrap(),
+                ),
+                &signer,
+            )
+            .unwrap();
+        }
+
+        let usdc = dex
+            .order_book("usdc", "untrn")
+            .unwrap()
+            .side("usdc")
+            .unwrap()
+            .clone();
+        let mut ticks: Vec<i64> = usdc.limit_orders.iter().map(|t| t.tick_index).collect();
+        ticks.sort();
+        assert_eq!(ticks, vec![-100_000, -10, 100_000]);
+
+        // zero fee pools sit at their center tick, up to the ends of the range
+        for tick_index in [-MAX_TICK_INDEX, -184_216, 184_216, MAX_TICK_INDEX] {
+            dex.provide_liquidity(
+                ("usdc", "untrn"),
+                tick_index,
+                &[0],
+                &[(Uint128::new(1_000_000), Uint128::zero())],
+                &signer,
+            )
+            .unwrap();
+        }
+
+        let usdc = dex
+            .order_book("usdc", "untrn")
+            .unwrap()
+            .side("usdc")
+            .unwrap()
+            .clone();
+        for tick_index in [-MAX_TICK_INDEX, MAX_TICK_INDEX] {
+            assert!(usdc.pools.iter().any(|pool| pool.tick_index == tick_index));
+        }
+
+        // the chain prices are exactly `price_from_tick`
+        for tranche in &usdc.limit_orders {
+            assert_eq!(
+                price_from_tick(tranche.tick_index).unwrap(),
+                tranche.price,
+                "tick {}",
+                tranche.tick_index
+            );
+        }
+        for pool in &usdc.pools {
+            assert_eq!(
+                price_from_tick(pool.tick_index).unwrap(),
+                pool.price,
+                "tick {}",
+                pool.tick_index
+            );
+        }
+
+        // fill or kill orders larger than the book are rejected
+        assert!(dex
+            .submit_limit_order(
+                &LimitOrder::fill_or_kill(
+                    "untrn",
+                    "usdc",
+                    100_000_000u128,
+                    price_from_tick(1_000).unwrap()
+                ),
+                &signer,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn dex_limit_order_expiry() {
+        use cosmwasm_std::Uint128;
+        use margined_neutron_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+
+        use crate::{price_from_tick, Bank, LimitOrder};
+
+        let app = NeutronTestApp::new();
+        let signer = app
+            .init_account(&[
+                Coin::new(1_000_000_000_000_000_000_000u128, "untrn"),
+                Coin::new(1_000_000_000_000u128, "usdc"),
+            ])
+            .unwrap();
+        let dex = Dex::new(&app);
+        let usdc_balance = || {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: signer.address(),
+                    denom: "usdc".to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+                .parse::<u128>()
+                .unwrap()
+        };
+        let price = price_from_tick(0).unwrap();
+
+        // only expiring orders are accepted
+        assert!(dex
+            .place_and_expire(
+                &LimitOrder::good_til_cancelled("usdc", "untrn", 1_000u128, price),
+                &signer,
+                10,
+            )
+            .is_err());
+
+        // just in time orders are purged at the end of the block they are placed in
+        let expired = dex
+            .place_and_expire(
+                &LimitOrder::just_in_time("usdc", "untrn", 1_000_000u128, price),
+                &signer,
+                10,
+            )
+            .unwrap();
+        assert_eq!(expired.unfilled, Coin::new(1_000_000u128, "usdc"));
+        assert_eq!(expired.filled, Coin::new(0u128, "untrn"));
+
+        // good til time orders are purged once the block time reaches their expiration
+        let expiration_time = app.get_block_timestamp().plus_seconds(3_600);
+        let expired = dex
+            .place_and_expire(
+                &LimitOrder::good_til_time("usdc", "untrn", 2_000_000u128, price, expiration_time),
+                &signer,
+                10,
+            )
+            .unwrap();
+        assert!(app.get_block_timestamp() >= expiration_time);
+        assert_eq!(expired.unfilled, Coin::new(2_000_000u128, "usdc"));
+
+        let balance = usdc_balance();
+        dex.withdraw_filled_limit_order(
+            DexTypes::MsgWithdrawFilledLimitOrder {
+                creator: signer.address(),
+                tranche_key: expired.tranche.key.unwrap().tranche_key,
+            },
+            &signer,
+        )
+        .unwrap();
+        assert_eq!(usdc_balance(), balance + 2_000_000);
+
+        // partly filled good til time orders pay out both the proceeds and the rest of the
+        // order, the maker's fees are paid by another account to compare exact balances
+        let payer = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let maker = app
+            .init_account(&[Coin::new(10_000_000u128, "usdc")])
+            .unwrap()
+            .with_fee_payer(payer);
+        let balance = |denom: &str| {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: maker.address(),
+                    denom: denom.to_string(),
+                })
+                .unwrap()
+                .balance
+                .map(|coin| coin.amount.parse::<u128>().unwrap())
+                .unwrap_or_default()
+        };
+
+        let expiration_time = app.get_block_timestamp().plus_seconds(3_600);
+        let tranche_key = dex
+            .submit_limit_order(
+                &LimitOrder::good_til_time("usdc", "untrn", 2_000_000u128, price, expiration_time),
+                &maker,
+            )
+            .unwrap()
+            .data
+            .tranche_key;
+        dex.submit_limit_order(
+            &LimitOrder::immediate_or_cancel(
+                "untrn",
+                "usdc",
+                500_000u128,
+                price_from_tick(10).unwrap(),
+            ),
+            &signer,
+        )
+        .unwrap();
+
+        app.advance_block(3_600).unwrap();
+        let expired = dex
+            .wait_for_expiry(&tranche_key, &maker.address(), 10)
+            .unwrap();
+        assert!(!expired.filled.amount.is_zero());
+        assert!(expired.unfilled.amount < Uint128::new(2_000_000));
+        assert!(!expired.unfilled.amount.is_zero());
+
+        let (usdc, untrn) = (balance("usdc"), balance("untrn"));
+        dex.withdraw_filled_limit_order(
+            DexTypes::MsgWithdrawFilledLimitOrder {
+                creator: maker.address(),
+                tranche_key,
+            },
+            &maker,
+        )
+        .unwrap();
+        assert_eq!(balance("usdc"), usdc + expired.unfilled.amount.u128());
+        assert_eq!(balance("untrn"), untrn + expired.filled.amount.u128());
+    }
 }
@@ -0,0 +1,345 @@
+use margined_neutron_std::types::cosmos::base::v1beta1::DecCoin;
+use margined_neutron_std::types::cosmos::distribution::v1beta1::{
+    MsgDepositValidatorRewardsPool, MsgDepositValidatorRewardsPoolResponse, MsgSetWithdrawAddress,
+    MsgSetWithdrawAddressResponse, MsgWithdrawDelegatorReward, MsgWithdrawDelegatorRewardResponse,
+    QueryCommunityPoolRequest, QueryCommunityPoolResponse, QueryDelegationRewardsRequest,
+    QueryDelegationRewardsResponse, QueryDelegationTotalRewardsRequest,
+    QueryDelegationTotalRewardsResponse, QueryDelegatorWithdrawAddressRequest,
+    QueryDelegatorWithdrawAddressResponse, QueryParamsRequest, QueryParamsResponse,
+    QueryValidatorOutstandingRewardsRequest, QueryValidatorOutstandingRewardsResponse,
+};
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::{NeutronTestApp, BLOCK_TIME_SECONDS};
+
+pub struct Distribution<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Distribution<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Distribution<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub withdraw_delegator_reward: MsgWithdrawDelegatorReward["/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward"] => MsgWithdrawDelegatorRewardResponse
+    }
+
+    fn_execute! {
+        pub set_withdraw_address: MsgSetWithdrawAddress["/cosmos.distribution.v1beta1.MsgSetWithdrawAddress"] => MsgSetWithdrawAddressResponse
+    }
+
+    fn_execute! {
+        pub deposit_validator_rewards_pool: MsgDepositValidatorRewardsPool["/cosmos.distribution.v1beta1.MsgDepositValidatorRewardsPool"] => MsgDepositValidatorRewardsPoolResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.distribution.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_delegation_rewards ["/cosmos.distribution.v1beta1.Query/DelegationRewards"]: QueryDelegationRewardsRequest => QueryDelegationRewardsResponse
+    }
+
+    fn_query! {
+        pub query_delegation_total_rewards ["/cosmos.distribution.v1beta1.Query/DelegationTotalRewards"]: QueryDelegationTotalRewardsRequest => QueryDelegationTotalRewardsResponse
+    }
+
+    fn_query! {
+        pub query_delegator_withdraw_address ["/cosmos.distribution.v1beta1.Query/DelegatorWithdrawAddress"]: QueryDelegatorWithdrawAddressRequest => QueryDelegatorWithdrawAddressResponse
+    }
+
+    fn_query! {
+        pub query_validator_outstanding_rewards ["/cosmos.distribution.v1beta1.Query/ValidatorOutstandingRewards"]: QueryValidatorOutstandingRewardsRequest => QueryValidatorOutstandingRewardsResponse
+    }
+
+    fn_query! {
+        pub query_community_pool ["/cosmos.distribution.v1beta1.Query/CommunityPool"]: QueryCommunityPoolRequest => QueryCommunityPoolResponse
+    }
+}
+
+impl<'a> Distribution<'a, NeutronTestApp> {
+    /// Produce blocks until the delegation of `delegator` to `validator` has pending
+    /// rewards, for at most `max_blocks` blocks, and return them.
+    ///
+    /// Rewards come from the fees distributed to the validator or from
+    /// [`Distribution::deposit_validator_rewards_pool`], so something must fund the
+    /// validator for this to succeed.
+    pub fn wait_for_rewards(
+        &self,
+        delegator: &str,
+        validator: &str,
+        max_blocks: u64,
+    ) -> RunnerResult<Vec<DecCoin>> {
+        let request = QueryDelegationRewardsRequest {
+            delegator_address: delegator.to_string(),
+            validator_address: validator.to_string(),
+        };
+
+        for _ in 0..max_blocks {
+            self.runner.advance_block(BLOCK_TIME_SECONDS)?;
+
+            let rewards = self.query_delegation_rewards(&request)?.rewards;
+            if !rewards.is_empty() {
+                return Ok(rewards);
+            }
+        }
+
+        Err(RunnerError::GenericError(format!(
+            "no rewards accrued for {} on {} within {} blocks",
+            delegator, validator, max_blocks
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use cosmwasm_std::{Coin, Decimal256, Uint128, Uint256};
+    use margined_neutron_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use margined_neutron_std::types::cosmos::distribution::v1beta1::{
+        MsgDepositValidatorRewardsPool, MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+        QueryDelegationRewardsRequest, QueryDelegatorWithdrawAddressRequest,
+    };
+    use margined_neutron_std::types::cosmos::staking::v1beta1::MsgDelegate;
+
+    use crate::{Account, Bank, Distribution, NeutronTestApp, Staking};
+    use test_tube_ntrn::{Module, RunnerError};
+
+    #[test]
+    fn distribution_withdraw_rewards() {
+        let app = NeutronTestApp::new();
+        let staking = Staking::new(&app);
+        let distribution = Distribution::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (delegator, recipient) = (&accs[0], &accs[1]);
+
+        let validator = staking
+            .bonded_validators()
+            .unwrap()
+            .remove(0)
+            .operator_address;
+
+        staking
+            .delegate(
+                MsgDelegate {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                    amount: Some(BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1000000".to_string(),
+                    }),
+                },
+                delegator,
+            )
+            .unwrap();
+
+        distribution
+            .set_withdraw_address(
+                MsgSetWithdrawAddress {
+                    delegator_address: delegator.address(),
+                    withdraw_address: recipient.address(),
+                },
+                delegator,
+            )
+            .unwrap();
+
+        let withdraw_address = distribution
+            .query_delegator_withdraw_address(&QueryDelegatorWithdrawAddressRequest {
+                delegator_address: delegator.address(),
+            })
+            .unwrap()
+            .withdraw_address;
+        assert_eq!(withdraw_address, recipient.address());
+
+        // rewards are tracked from the delegation height
+        assert!(distribution
+            .query_delegation_rewards(&QueryDelegationRewardsRequest {
+                delegator_address: delegator.address(),
+                validator_address: validator.clone(),
+            })
+            .is_ok());
+
+        distribution
+            .withdraw_delegator_reward(
+                MsgWithdrawDelegatorReward {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                },
+                delegator,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn distribution_wait_for_rewards_timeout() {
+        let app = NeutronTestApp::new();
+        let staking = Staking::new(&app);
+        let distribution = Distribution::new(&app);
+
+        let delegator = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let validator = staking
+            .bonded_validators()
+            .unwrap()
+            .remove(0)
+            .operator_address;
+
+        staking
+            .delegate(
+                MsgDelegate {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                    amount: Some(BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "100000000000".to_string(),
+                    }),
+                },
+                &delegator,
+            )
+            .unwrap();
+
+        // nothing funds the validator, so the delegation exists but earns nothing
+        let err = distribution
+            .wait_for_rewards(&delegator.address(), &validator, 3)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RunnerError::GenericError(msg) if msg == format!(
+                "no rewards accrued for {} on {} within 3 blocks",
+                delegator.address(),
+                validator
+            )
+        ));
+        assert!(distribution
+            .query_delegation_rewards(&QueryDelegationRewardsRequest {
+                delegator_address: delegator.address(),
+                validator_address: validator,
+            })
+            .unwrap()
+            .rewards
+            .is_empty());
+    }
+
+    #[test]
+    fn distribution_wait_for_rewards() {
+        let app = NeutronTestApp::new();
+        let staking = Staking::new(&app);
+        let distribution = Distribution::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (delegator, recipient) = (&accs[0], &accs[1]);
+
+        let validator = staking
+            .bonded_validators()
+            .unwrap()
+            .remove(0)
+            .operator_address;
+
+        staking
+            .delegate(
+                MsgDelegate {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                    amount: Some(BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "100000000000".to_string(),
+                    }),
+                },
+                delegator,
+            )
+            .unwrap();
+        distribution
+            .set_withdraw_address(
+                MsgSetWithdrawAddress {
+                    delegator_address: delegator.address(),
+                    withdraw_address: recipient.address(),
+                },
+                delegator,
+            )
+            .unwrap();
+
+        // fund the validator so its delegators earn rewards
+        distribution
+            .deposit_validator_rewards_pool(
+                MsgDepositValidatorRewardsPool {
+                    depositor: delegator.address(),
+                    validator_address: validator.clone(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "100000000000".to_string(),
+                    }],
+                },
+                delegator,
+            )
+            .unwrap();
+
+        let rewards = distribution
+            .wait_for_rewards(&delegator.address(), &validator, 10)
+            .unwrap();
+        // proto encoded decimals carry their 18 decimal places as plain digits
+        let pending = rewards
+            .iter()
+            .find(|reward| reward.denom == "untrn")
+            .map(|reward| match reward.amount.contains('.') {
+                true => Decimal256::from_str(&reward.amount).unwrap(),
+                false => Decimal256::new(Uint256::from_str(&reward.amount).unwrap()),
+            })
+            .unwrap();
+        assert!(!pending.is_zero());
+
+        let balance = |address: String| -> u128 {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address,
+                    denom: "untrn".to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+                .parse()
+                .unwrap()
+        };
+        let before = balance(recipient.address());
+
+        let withdrawn = distribution
+            .withdraw_delegator_reward(
+                MsgWithdrawDelegatorReward {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                },
+                delegator,
+            )
+            .unwrap()
+            .data
+            .amount
+            .into_iter()
+            .find(|coin| coin.denom == "untrn")
+            .map(|coin| coin.amount.parse::<u128>().unwrap())
+            .unwrap();
+
+        // the withdrawn rewards include at least the pending ones and reach the withdraw address
+        assert!(Uint128::new(withdrawn) >= pending.to_uint_floor().try_into().unwrap());
+        assert_eq!(balance(recipient.address()), before + withdrawn);
+    }
+}
@@ -0,0 +1,199 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use margined_neutron_std::types::neutron::feeburner::{
+    QueryParamsRequest, QueryParamsResponse, QueryTotalBurnedNeutronsAmountRequest,
+    QueryTotalBurnedNeutronsAmountResponse,
+};
+use test_tube_ntrn::fn_query;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+const CONSUMER_PARAMS_QUERY_PATH: &str = "/interchain_security.ccv.consumer.v1.Query/QueryParams";
+
+/// `interchain_security.ccv.consumer.v1.QueryParamsRequest`
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct ConsumerParamsRequest {}
+
+/// `interchain_security.ccv.consumer.v1.QueryParamsResponse`
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct ConsumerParamsResponse {
+    #[prost(message, optional, tag = "1")]
+    params: ::core::option::Option<ConsumerParams>,
+}
+
+/// The fields of `interchain_security.ccv.v1.ConsumerParams` needed to split fees.
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct ConsumerParams {
+    /// Share of the fees kept by Neutron, the rest being sent to the provider chain.
+    #[prost(string, tag = "7")]
+    consumer_redistribution_fraction: ::prost::alloc::string::String,
+}
+
+pub struct FeeBurner<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for FeeBurner<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> FeeBurner<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_query! {
+        pub query_params ["/neutron.feeburner.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_total_burned_neutrons_amount ["/neutron.feeburner.Query/TotalBurnedNeutronsAmount"]: QueryTotalBurnedNeutronsAmountRequest => QueryTotalBurnedNeutronsAmountResponse
+    }
+
+    /// Total amount of `untrn` burned so far.
+    pub fn total_burned(&self) -> RunnerResult<Uint128> {
+        let amount = self
+            .query_total_burned_neutrons_amount(&QueryTotalBurnedNeutronsAmountRequest {})?
+            .total_burned_neutrons_amount
+            .and_then(|total| total.coin)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+
+        if amount.is_empty() {
+            return Ok(Uint128::zero());
+        }
+
+        Uint128::from_str(&amount).map_err(|e| RunnerError::GenericError(e.to_string()))
+    }
+
+    /// Expected split of a tx `fee` with the current feeburner params and consumer
+    /// redistribution fraction of the ccv consumer module.
+    pub fn fee_split(&self, fee: &Coin) -> RunnerResult<FeeSplit> {
+        let params = self
+            .query_params(&QueryParamsRequest {})?
+            .params
+            .ok_or_else(|| RunnerError::GenericError("no feeburner params".to_string()))?;
+
+        let fraction = self
+            .runner
+            .query::<_, ConsumerParamsResponse>(
+                CONSUMER_PARAMS_QUERY_PATH,
+                &ConsumerParamsRequest {},
+            )?
+            .params
+            .ok_or_else(|| RunnerError::GenericError("no ccv consumer params".to_string()))?
+            .consumer_redistribution_fraction;
+        let fraction = Decimal::from_str(&fraction).map_err(|e| RunnerError::QueryError {
+            msg: format!(
+                "invalid consumer redistribution fraction `{}`: {}",
+                fraction, e
+            ),
+        })?;
+
+        Ok(FeeSplit::new(fee, &params.neutron_denom, fraction))
+    }
+}
+
+/// Where a tx fee ends up once distributed.
+///
+/// The consumer module keeps `redistribution_fraction` of the fee (truncated) and sends the
+/// rest to the provider chain. The kept part is burned by the feeburner when it is in the
+/// Neutron denom, and sent to the treasury otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub burned: Coin,
+    pub treasury: Coin,
+    pub provider: Coin,
+}
+
+impl FeeSplit {
+    pub fn new(fee: &Coin, neutron_denom: &str, redistribution_fraction: Decimal) -> Self {
+        let kept = fee.amount.mul_floor(redistribution_fraction);
+        let provider = Coin::new(fee.amount - kept, fee.denom.clone());
+
+        let (burned, treasury) = if fee.denom == neutron_denom {
+            (kept, Uint128::zero())
+        } else {
+            (Uint128::zero(), kept)
+        };
+
+        FeeSplit {
+            burned: Coin::new(burned, fee.denom.clone()),
+            treasury: Coin::new(treasury, fee.denom.clone()),
+            provider,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use cosmwasm_std::{coin, Coin, Decimal};
+    use margined_neutron_std::types::cosmos::bank::v1beta1::MsgSend;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+
+    use super::FeeSplit;
+    use crate::{Account, Bank, FeeBurner, NeutronTestApp};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn fee_burner_split() {
+        let app = NeutronTestApp::new();
+        let fee_burner = FeeBurner::new(&app);
+
+        let params = fee_burner
+            .query_params(&Default::default())
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(params.neutron_denom, "untrn");
+
+        let split = fee_burner.fee_split(&coin(1_001, "untrn")).unwrap();
+        assert_eq!(
+            split,
+            FeeSplit {
+                burned: coin(750, "untrn"),
+                treasury: coin(0, "untrn"),
+                provider: coin(251, "untrn"),
+            }
+        );
+
+        let split = FeeSplit::new(
+            &coin(1_000, "uatom"),
+            "untrn",
+            Decimal::from_str("0.5").unwrap(),
+        );
+        assert_eq!(split.burned, coin(0, "uatom"));
+        assert_eq!(split.treasury, coin(500, "uatom"));
+        assert_eq!(split.provider, coin(500, "uatom"));
+
+        // fees paid in untrn are burned at the end of the block
+        let burned_before = fee_burner.total_burned().unwrap();
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        Bank::new(&app)
+            .send(
+                MsgSend {
+                    from_address: accs[0].address(),
+                    to_address: accs[1].address(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1".to_string(),
+                    }],
+                },
+                &accs[0],
+            )
+            .unwrap();
+        app.increase_time(1);
+
+        assert!(fee_burner.total_burned().unwrap() > burned_before);
+    }
+}
@@ -0,0 +1,313 @@
+use margined_neutron_std::shim::Any;
+use margined_neutron_std::types::cosmos::feegrant::v1beta1::{
+    AllowedMsgAllowance, BasicAllowance, MsgGrantAllowance, MsgGrantAllowanceResponse,
+    MsgRevokeAllowance, MsgRevokeAllowanceResponse, PeriodicAllowance, QueryAllowanceRequest,
+    QueryAllowanceResponse, QueryAllowancesByGranterRequest, QueryAllowancesByGranterResponse,
+    QueryAllowancesRequest, QueryAllowancesResponse,
+};
+use prost::Message;
+use test_tube_ntrn::account::{Account, SigningAccount};
+use test_tube_ntrn::runner::result::RunnerExecuteResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+pub struct FeeGrant<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for FeeGrant<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> FeeGrant<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub grant_allowance: MsgGrantAllowance["/cosmos.feegrant.v1beta1.MsgGrantAllowance"] => MsgGrantAllowanceResponse
+    }
+
+    fn_execute! {
+        pub revoke_allowance: MsgRevokeAllowance["/cosmos.feegrant.v1beta1.MsgRevokeAllowance"] => MsgRevokeAllowanceResponse
+    }
+
+    fn_query! {
+        pub query_allowance ["/cosmos.feegrant.v1beta1.Query/Allowance"]: QueryAllowanceRequest => QueryAllowanceResponse
+    }
+
+    fn_query! {
+        pub query_allowances ["/cosmos.feegrant.v1beta1.Query/Allowances"]: QueryAllowancesRequest => QueryAllowancesResponse
+    }
+
+    fn_query! {
+        pub query_allowances_by_granter ["/cosmos.feegrant.v1beta1.Query/AllowancesByGranter"]: QueryAllowancesByGranterRequest => QueryAllowancesByGranterResponse
+    }
+
+    /// Let `grantee` pay its fees from the balance of `granter` within `allowance`. The
+    /// grantee's txs use it once signed by an account built
+    /// [`with_fee_granter`](SigningAccount::with_fee_granter).
+    pub fn grant(
+        &self,
+        granter: &SigningAccount,
+        grantee: &str,
+        allowance: FeeAllowance,
+    ) -> RunnerExecuteResult<MsgGrantAllowanceResponse> {
+        self.grant_allowance(
+            MsgGrantAllowance {
+                granter: granter.address(),
+                grantee: grantee.to_string(),
+                allowance: Some(allowance.to_any()),
+            },
+            granter,
+        )
+    }
+}
+
+/// Fee allowances supported by the feegrant module.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeeAllowance {
+    Basic(BasicAllowance),
+    Periodic(PeriodicAllowance),
+    /// Restrict `allowance` to the messages with the given type urls.
+    AllowedMsg {
+        allowance: Box<FeeAllowance>,
+        allowed_messages: Vec<String>,
+    },
+}
+
+impl FeeAllowance {
+    pub fn to_any(&self) -> Any {
+        match self {
+            FeeAllowance::Basic(allowance) => Any {
+                type_url: "/cosmos.feegrant.v1beta1.BasicAllowance".to_string(),
+                value: allowance.encode_to_vec(),
+            },
+            FeeAllowance::Periodic(allowance) => Any {
+                type_url: "/cosmos.feegrant.v1beta1.PeriodicAllowance".to_string(),
+                value: allowance.encode_to_vec(),
+            },
+            FeeAllowance::AllowedMsg {
+                allowance,
+                allowed_messages,
+            } => Any {
+                type_url: "/cosmos.feegrant.v1beta1.AllowedMsgAllowance".to_string(),
+                value: AllowedMsgAllowance {
+                    allowance: Some(allowance.to_any()),
+                    allowed_messages: allowed_messages.clone(),
+                }
+                .encode_to_vec(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::shim::Duration;
+    use margined_neutron_std::types::cosmos::bank::v1beta1::{MsgSend, QueryBalanceRequest};
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use margined_neutron_std::types::cosmos::feegrant::v1beta1::{
+        BasicAllowance, MsgRevokeAllowance, PeriodicAllowance, QueryAllowanceRequest,
+    };
+    use margined_neutron_std::types::osmosis::tokenfactory::v1beta1::MsgCreateDenom;
+    use prost::Message;
+
+    use super::FeeAllowance;
+    use crate::{Account, Bank, FeeGrant, NeutronTestApp, SigningAccount, TokenFactory};
+    use test_tube_ntrn::Module;
+
+    fn balance(app: &NeutronTestApp, address: &str) -> u128 {
+        Bank::new(app)
+            .query_balance(&QueryBalanceRequest {
+                address: address.to_string(),
+                denom: "untrn".to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap()
+            .amount
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn fee_grant_pays_grantee_fees() {
+        let app = NeutronTestApp::new();
+        let fee_grant = FeeGrant::new(&app);
+        let tokenfactory = TokenFactory::new(&app);
+
+        let granter = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let grantees = app.init_accounts(&[Coin::new(1u128, "untrn")], 3).unwrap();
+        let addresses: Vec<String> = grantees.iter().map(|g| g.address()).collect();
+
+        let create_denom = |grantee: &SigningAccount, subdenom: &str| {
+            tokenfactory.create_denom(
+                MsgCreateDenom {
+                    sender: grantee.address(),
+                    subdenom: subdenom.to_string(),
+                },
+                grantee,
+            )
+        };
+
+        let spend_limit = 1_000_000_000u128;
+        fee_grant
+            .grant(
+                &granter,
+                &addresses[0],
+                FeeAllowance::Basic(BasicAllowance {
+                    spend_limit: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: spend_limit.to_string(),
+                    }],
+                    expiration: None,
+                }),
+            )
+            .unwrap();
+
+        // fees are paid by the granter and deducted from the allowance
+        let mut grantees = grantees.into_iter();
+        let grantee = grantees.next().unwrap().with_fee_granter(granter.address());
+        let granter_balance = balance(&app, &granter.address());
+        create_denom(&grantee, "granted").unwrap();
+
+        let paid = granter_balance - balance(&app, &granter.address());
+        assert!(paid > 0);
+        assert_eq!(balance(&app, &addresses[0]), 1);
+
+        let allowance = fee_grant
+            .query_allowance(&QueryAllowanceRequest {
+                granter: granter.address(),
+                grantee: addresses[0].clone(),
+            })
+            .unwrap()
+            .allowance
+            .unwrap()
+            .allowance
+            .unwrap();
+        let allowance = BasicAllowance::decode(allowance.value.as_slice()).unwrap();
+        assert_eq!(
+            allowance.spend_limit[0].amount,
+            (spend_limit - paid).to_string()
+        );
+
+        // revoked allowances can no longer be used
+        fee_grant
+            .revoke_allowance(
+                MsgRevokeAllowance {
+                    granter: granter.address(),
+                    grantee: addresses[0].clone(),
+                },
+                &granter,
+            )
+            .unwrap();
+        assert!(create_denom(&grantee, "revoked").is_err());
+
+        // allowed msg allowances only pay for the listed messages
+        fee_grant
+            .grant(
+                &granter,
+                &addresses[1],
+                FeeAllowance::AllowedMsg {
+                    allowance: Box::new(FeeAllowance::Basic(BasicAllowance::default())),
+                    allowed_messages: vec!["/cosmos.bank.v1beta1.MsgSend".to_string()],
+                },
+            )
+            .unwrap();
+        let grantee = grantees.next().unwrap().with_fee_granter(granter.address());
+        assert!(create_denom(&grantee, "notallowed").is_err());
+
+        // a fee payer co-signs and pays the fees
+        let payer = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let payer_address = payer.address();
+        let grantee = grantees.next().unwrap().with_fee_payer(payer);
+        create_denom(&grantee, "payer").unwrap();
+        assert_eq!(balance(&app, &addresses[2]), 1);
+        assert!(balance(&app, &payer_address) < 1_000_000_000_000);
+    }
+
+    #[test]
+    fn fee_grant_periodic_allowance_exhausts_period_limit() {
+        let app = NeutronTestApp::new();
+        let fee_grant = FeeGrant::new(&app);
+        let bank = Bank::new(&app);
+
+        let granter = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let grantees = app.init_accounts(&[Coin::new(1u128, "untrn")], 2).unwrap();
+        let addresses: Vec<String> = grantees.iter().map(|g| g.address()).collect();
+        let mut grantees = grantees
+            .into_iter()
+            .map(|grantee| grantee.with_fee_granter(granter.address()));
+
+        let send_to_self = |account: &SigningAccount| {
+            bank.send(
+                MsgSend {
+                    from_address: account.address(),
+                    to_address: account.address(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1".to_string(),
+                    }],
+                },
+                account,
+            )
+        };
+
+        // fee of a granted self send, measured with an unlimited allowance
+        fee_grant
+            .grant(
+                &granter,
+                &addresses[0],
+                FeeAllowance::Basic(BasicAllowance::default()),
+            )
+            .unwrap();
+        let granter_balance = balance(&app, &granter.address());
+        send_to_self(&grantees.next().unwrap()).unwrap();
+        let fee = granter_balance - balance(&app, &granter.address());
+
+        // the period limit covers one fee but not two
+        let period_limit = vec![BaseCoin {
+            denom: "untrn".to_string(),
+            amount: (fee * 3 / 2).to_string(),
+        }];
+        let period = 3_600;
+        fee_grant
+            .grant(
+                &granter,
+                &addresses[1],
+                FeeAllowance::Periodic(PeriodicAllowance {
+                    basic: Some(BasicAllowance::default()),
+                    period: Some(Duration {
+                        seconds: period as i64,
+                        nanos: 0,
+                    }),
+                    period_spend_limit: period_limit.clone(),
+                    period_can_spend: period_limit,
+                    period_reset: None,
+                }),
+            )
+            .unwrap();
+
+        let grantee = grantees.next().unwrap();
+        send_to_self(&grantee).unwrap();
+        let err = send_to_self(&grantee).unwrap_err();
+        assert!(err.to_string().contains("fee limit exceeded"));
+        assert_eq!(balance(&app, &addresses[1]), 1);
+
+        // the limit is restored once the period is over
+        app.advance_block(period).unwrap();
+        send_to_self(&grantee).unwrap();
+        assert_eq!(balance(&app, &addresses[1]), 1);
+    }
+}
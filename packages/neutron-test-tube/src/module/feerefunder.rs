@@ -0,0 +1,183 @@
+use margined_neutron_std::types::neutron::feerefunder::{
+    FeeInfoRequest, FeeInfoResponse, QueryParamsRequest, QueryParamsResponse,
+};
+use test_tube_ntrn::fn_query;
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+pub struct FeeRefunder<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for FeeRefunder<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> FeeRefunder<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_query! {
+        pub query_params ["/neutron.feerefunder.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_fee_info ["/neutron.feerefunder.Query/FeeInfo"]: FeeInfoRequest => FeeInfoResponse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Coin};
+    use margined_neutron_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use margined_neutron_std::types::neutron::feerefunder::{FeeInfoRequest, QueryParamsRequest};
+    use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
+    use neutron_sdk::sudo::msg::RequestPacketTimeoutHeight;
+
+    use crate::sudo_recorder::SudoRecorder;
+    use crate::{Account, Bank, FeeRefunder, IbcPacket, NeutronTestApp, PacketOutcome, Transfer};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn fee_refunder_queries() {
+        let app = NeutronTestApp::new();
+        let fee_refunder = FeeRefunder::new(&app);
+
+        let min_fee = fee_refunder
+            .query_params(&QueryParamsRequest {})
+            .unwrap()
+            .params
+            .unwrap()
+            .min_fee
+            .unwrap();
+        assert!(min_fee.recv_fee.is_empty());
+        assert!(!min_fee.ack_fee.is_empty());
+        assert!(!min_fee.timeout_fee.is_empty());
+
+        // no fee is locked for packets that were never sent
+        assert!(fee_refunder
+            .query_fee_info(&FeeInfoRequest {
+                channel_id: "channel-0".to_string(),
+                port_id: "transfer".to_string(),
+                sequence: 1,
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn fee_refunder_contract_refunds() {
+        let app = NeutronTestApp::new();
+        let fee_refunder = FeeRefunder::new(&app);
+        let transfer = Transfer::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (owner, relayer) = (&accs[0], &accs[1]);
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+
+        let recorder = SudoRecorder::new(&app, &[Coin::new(10_000_000u128, "untrn")], owner);
+        let balance = |address: &str| -> u128 {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: address.to_string(),
+                    denom: "untrn".to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+                .parse()
+                .unwrap()
+        };
+
+        for (outcome, payout, refund) in [
+            (PacketOutcome::transfer_success(), 1_000u128, 2_000u128),
+            (PacketOutcome::Timeout, 2_000u128, 1_000u128),
+        ] {
+            let res = recorder
+                .dispatch(
+                    vec![NeutronMsg::IbcTransfer {
+                        source_port: "transfer".to_string(),
+                        source_channel: channel_id.clone(),
+                        token: coin(1, "untrn"),
+                        sender: recorder.address.clone(),
+                        receiver: "cosmos1receiver".to_string(),
+                        timeout_height: RequestPacketTimeoutHeight {
+                            revision_number: None,
+                            revision_height: None,
+                        },
+                        timeout_timestamp: app.get_block_time_nanos() as u64 + 3_600_000_000_000,
+                        memo: String::new(),
+                        fee: IbcFee {
+                            recv_fee: vec![],
+                            ack_fee: vec![coin(1_000, "untrn")],
+                            timeout_fee: vec![coin(2_000, "untrn")],
+                        },
+                    }
+                    .into()],
+                    owner,
+                )
+                .unwrap();
+            let packet = IbcPacket::from_events(&res.events).unwrap().remove(0);
+
+            // both fees are locked until the packet is answered
+            let fee_info = fee_refunder
+                .query_fee_info(&FeeInfoRequest {
+                    channel_id: channel_id.clone(),
+                    port_id: "transfer".to_string(),
+                    sequence: packet.sequence,
+                })
+                .unwrap()
+                .fee_info
+                .unwrap();
+            assert_eq!(fee_info.payer, recorder.address);
+            let fee = fee_info.fee.unwrap();
+            assert_eq!(
+                fee.ack_fee,
+                vec![BaseCoin {
+                    denom: "untrn".to_string(),
+                    amount: "1000".to_string(),
+                }]
+            );
+            assert_eq!(
+                fee.timeout_fee,
+                vec![BaseCoin {
+                    denom: "untrn".to_string(),
+                    amount: "2000".to_string(),
+                }]
+            );
+
+            let contract_before = balance(&recorder.address);
+            let relayer_before = balance(&relayer.address());
+            transfer
+                .relay(&res.events, &outcome, &relayer.address())
+                .unwrap();
+
+            // the relayer is paid the fee of the outcome and the contract refunded the other
+            assert_eq!(balance(&relayer.address()), relayer_before + payout);
+            let refunded_tokens = if outcome == PacketOutcome::Timeout {
+                1
+            } else {
+                0
+            };
+            assert_eq!(
+                balance(&recorder.address),
+                contract_before + refund + refunded_tokens
+            );
+            assert!(fee_refunder
+                .query_fee_info(&FeeInfoRequest {
+                    channel_id: channel_id.clone(),
+                    port_id: "transfer".to_string(),
+                    sequence: packet.sequence,
+                })
+                .is_err());
+        }
+    }
+}
@@ -1,12 +1,18 @@
+use std::str::FromStr;
+
 use cosmrs::tx::MessageExt;
+use cosmrs::AccountId;
 use margined_neutron_std::shim::Any;
 use margined_neutron_std::types::cosmos::{
     adminmodule::adminmodule::MsgSubmitProposal,
+    base::v1beta1::Coin as BaseCoin,
     gov::{
         v1::{
-            MsgSubmitProposalResponse, MsgVote, MsgVoteResponse, QueryParamsRequest,
-            QueryParamsResponse, QueryProposalRequest, QueryProposalResponse,
-            QueryProposalsRequest, QueryProposalsResponse, VoteOption,
+            self, MsgDeposit, MsgDepositResponse, MsgSubmitProposalResponse, MsgVote,
+            MsgVoteResponse, Params, ProposalStatus, QueryDepositsRequest, QueryDepositsResponse,
+            QueryParamsRequest, QueryParamsResponse, QueryProposalRequest, QueryProposalResponse,
+            QueryProposalsRequest, QueryProposalsResponse, QueryTallyResultRequest,
+            QueryTallyResultResponse, TallyResult, VoteOption,
         },
         v1beta1,
     },
@@ -14,10 +20,10 @@ use margined_neutron_std::types::cosmos::{
 use test_tube_ntrn::module::Module;
 use test_tube_ntrn::runner::Runner;
 use test_tube_ntrn::{
-    fn_execute, fn_query, Account, RunnerError, RunnerExecuteResult, SigningAccount,
+    fn_execute, fn_query, Account, RunnerError, RunnerExecuteResult, RunnerResult, SigningAccount,
 };
 
-use crate::NeutronTestApp;
+use crate::{NeutronTestApp, Staking};
 
 pub struct Gov<'a, R: Runner<'a>> {
     runner: &'a R,
@@ -37,25 +43,43 @@ where
         pub submit_proposal: MsgSubmitProposal => MsgSubmitProposalResponse
     }
 
+    fn_execute! {
+        pub submit_proposal_v1: v1::MsgSubmitProposal => MsgSubmitProposalResponse
+    }
+
     fn_execute! {
         pub submit_proposal_v1beta1: v1beta1::MsgSubmitProposal => v1beta1::MsgSubmitProposalResponse
 
     }
 
+    fn_execute! {
+        pub deposit: MsgDeposit => MsgDepositResponse
+    }
+
     fn_execute! {
         pub vote: MsgVote => MsgVoteResponse
     }
 
     fn_query! {
-        pub query_proposal ["/cosmos.gov.v1beta1.Query/Proposal"]: QueryProposalRequest => QueryProposalResponse
+        pub query_proposal ["/cosmos.gov.v1.Query/Proposal"]: QueryProposalRequest => QueryProposalResponse
+    }
+
+    fn_query! {
+        pub query_proposals ["/cosmos.gov.v1.Query/Proposals"]: QueryProposalsRequest => QueryProposalsResponse,
+        all_pages: query_proposals_all_pages => proposals
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.gov.v1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
     }
 
     fn_query! {
-        pub query_proposals ["/cosmos.gov.v1beta1.Query/Proposals"]: QueryProposalsRequest => QueryProposalsResponse
+        pub query_deposits ["/cosmos.gov.v1.Query/Deposits"]: QueryDepositsRequest => QueryDepositsResponse,
+        all_pages: query_deposits_all_pages => deposits
     }
 
     fn_query! {
-        pub query_params ["/cosmos.gov.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+        pub query_tally_result ["/cosmos.gov.v1.Query/TallyResult"]: QueryTallyResultRequest => QueryTallyResultResponse
     }
 
     pub fn submit_executable_proposal<M: prost::Message>(
@@ -80,6 +104,28 @@ where
     }
 }
 
+/// Options for a proposal driven through [`GovWithAppAccess::run_proposal`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProposalOptions {
+    pub title: String,
+    pub summary: String,
+    pub metadata: String,
+    pub expedited: bool,
+    /// Deposit sent along with the proposal, defaults to the (expedited) minimum deposit.
+    /// Anything missing is topped up by the proposer before voting starts.
+    pub initial_deposit: Option<Vec<BaseCoin>>,
+}
+
+/// Final state of a proposal after its voting period ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposalOutcome {
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+    pub final_tally_result: Option<TallyResult>,
+    /// Why the proposal messages failed to execute, empty unless `status` is `Failed`.
+    pub failed_reason: String,
+}
+
 /// Extension for Gov module
 /// It has ability to access to `NeutronTestApp` which is more specific than `Runner`
 pub struct GovWithAppAccess<'a> {
@@ -99,6 +145,9 @@ impl<'a> GovWithAppAccess<'a> {
         &self.gov
     }
 
+    /// Submit a `cosmos.gov.v1` proposal with the minimum deposit, pass it with `Yes` votes
+    /// and wait for it to be executed.
+    /// Returns an error if the proposal did not end up `Passed`.
     pub fn propose_and_execute<M: prost::Message>(
         &self,
         msg_type_url: String,
@@ -106,42 +155,379 @@ impl<'a> GovWithAppAccess<'a> {
         proposer: String,
         signer: &SigningAccount,
     ) -> RunnerExecuteResult<MsgSubmitProposalResponse> {
-        // submit proposal
-        let submit_proposal_res = self.gov.submit_proposal(
-            MsgSubmitProposal {
-                messages: vec![Any {
-                    type_url: msg_type_url,
-                    value: msg
-                        .to_bytes()
-                        .map_err(|e| RunnerError::EncodeError(e.into()))?,
-                }],
+        let messages = vec![Any {
+            type_url: msg_type_url,
+            value: msg
+                .to_bytes()
+                .map_err(|e| RunnerError::EncodeError(e.into()))?,
+        }];
+
+        let submit_proposal_res =
+            self.submit_with_deposit(messages, &ProposalOptions::default(), proposer, signer)?;
+        let outcome = self.vote_and_wait(submit_proposal_res.data.proposal_id, VoteOption::Yes)?;
+
+        if outcome.status != ProposalStatus::Passed {
+            return Err(RunnerError::ExecuteError {
+                msg: format!(
+                    "proposal {} ended with status {:?}: {}",
+                    outcome.proposal_id, outcome.status, outcome.failed_reason
+                ),
+            });
+        }
+
+        Ok(submit_proposal_res)
+    }
+
+    /// Run the full proposal lifecycle: submit with deposit, vote `vote` with every validator,
+    /// fast-forward past the voting period and report how the proposal ended.
+    pub fn run_proposal(
+        &self,
+        messages: Vec<Any>,
+        vote: VoteOption,
+        options: ProposalOptions,
+        proposer: &SigningAccount,
+    ) -> RunnerResult<ProposalOutcome> {
+        let proposal_id = self
+            .submit_with_deposit(messages, &options, proposer.address(), proposer)?
+            .data
+            .proposal_id;
+
+        self.vote_and_wait(proposal_id, vote)
+    }
+
+    /// Current gov module params.
+    pub fn params(&self) -> RunnerResult<Params> {
+        self.gov
+            .query_params(&QueryParamsRequest {
+                params_type: "".to_string(),
+            })?
+            .params
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: "gov params not found".to_string(),
+            })
+    }
+
+    /// Current status of a proposal.
+    pub fn proposal_status(&self, proposal_id: u64) -> RunnerResult<ProposalStatus> {
+        Ok(self.outcome(proposal_id)?.status)
+    }
+
+    fn submit_with_deposit(
+        &self,
+        messages: Vec<Any>,
+        options: &ProposalOptions,
+        proposer: String,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgSubmitProposalResponse> {
+        let params = self.params()?;
+        let min_deposit = if options.expedited {
+            params.expedited_min_deposit
+        } else {
+            params.min_deposit
+        };
+
+        let initial_deposit = options
+            .initial_deposit
+            .clone()
+            .unwrap_or_else(|| min_deposit.clone());
+
+        let res = self.gov.submit_proposal_v1(
+            v1::MsgSubmitProposal {
+                messages,
+                initial_deposit: initial_deposit.clone(),
                 proposer,
+                metadata: options.metadata.clone(),
+                title: options.title.clone(),
+                summary: options.summary.clone(),
+                expedited: options.expedited,
             },
             signer,
         )?;
 
-        let proposal_id = submit_proposal_res.data.proposal_id;
+        let missing = missing_deposit(&min_deposit, &initial_deposit);
+        if !missing.is_empty() {
+            self.gov.deposit(
+                MsgDeposit {
+                    proposal_id: res.data.proposal_id,
+                    depositor: signer.address(),
+                    amount: missing,
+                },
+                signer,
+            )?;
+        }
 
-        // get validator to vote yes for proposal
-        let val = self
-            .app
-            .get_first_validator_signing_account("untrn".to_string(), 1.3)?;
+        Ok(res)
+    }
 
-        self.gov
-            .vote(
+    fn vote_and_wait(&self, proposal_id: u64, vote: VoteOption) -> RunnerResult<ProposalOutcome> {
+        for validator in self.validators()? {
+            self.gov.vote(
                 MsgVote {
                     proposal_id,
-                    voter: val.address(),
-                    option: VoteOption::Yes.into(),
+                    voter: validator.address(),
+                    option: vote.into(),
                     metadata: "".to_string(),
                 },
-                &val,
+                &validator,
+            )?;
+        }
+
+        let params = self.params()?;
+        let expedited = self
+            .gov
+            .query_proposal(&QueryProposalRequest { proposal_id })?
+            .proposal
+            .map(|p| p.expedited)
+            .unwrap_or_default();
+        let voting_period = if expedited {
+            params.expedited_voting_period
+        } else {
+            params.voting_period
+        }
+        .map(|d| d.seconds as u64)
+        .unwrap_or_default();
+
+        // the proposal is tallied in the end blocker of the first block past its voting period
+        self.app.advance_block(voting_period + 1)?;
+
+        self.outcome(proposal_id)
+    }
+
+    fn outcome(&self, proposal_id: u64) -> RunnerResult<ProposalOutcome> {
+        let proposal = self
+            .gov
+            .query_proposal(&QueryProposalRequest { proposal_id })?
+            .proposal
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("proposal {} not found", proposal_id),
+            })?;
+
+        Ok(ProposalOutcome {
+            proposal_id,
+            status: ProposalStatus::try_from(proposal.status)
+                .unwrap_or(ProposalStatus::Unspecified),
+            final_tally_result: proposal.final_tally_result,
+            failed_reason: proposal.failed_reason,
+        })
+    }
+
+    /// Only the genesis validator has a known key, so votes are cast by it alone.
+    /// That decides the proposal only while it is the single bonded validator, which
+    /// is checked here rather than silently tallying an incomplete vote.
+    fn validators(&self) -> RunnerResult<Vec<SigningAccount>> {
+        let validator = self
+            .app
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)?;
+        let operator = validator.account_id().to_bytes();
+
+        for bonded in Staking::new(self.app).bonded_validators()? {
+            let is_genesis_validator = AccountId::from_str(&bonded.operator_address)
+                .map(|id| id.to_bytes() == operator)
+                .unwrap_or(false);
+            if !is_genesis_validator {
+                return Err(RunnerError::GenericError(format!(
+                    "validator {} is bonded but cannot vote, only the genesis validator signs votes",
+                    bonded.operator_address
+                )));
+            }
+        }
+
+        Ok(vec![validator])
+    }
+}
+
+fn missing_deposit(min_deposit: &[BaseCoin], deposited: &[BaseCoin]) -> Vec<BaseCoin> {
+    min_deposit
+        .iter()
+        .filter_map(|min| {
+            let required: u128 = min.amount.parse().unwrap_or_default();
+            let paid: u128 = deposited
+                .iter()
+                .filter(|c| c.denom == min.denom)
+                .map(|c| c.amount.parse::<u128>().unwrap_or_default())
+                .sum();
+
+            (paid < required).then(|| BaseCoin {
+                denom: min.denom.clone(),
+                amount: (required - paid).to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::shim::Any;
+    use margined_neutron_std::types::cosmos::bank::v1beta1::MsgSend;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use margined_neutron_std::types::cosmos::gov::v1::{
+        ProposalStatus, QueryDepositsRequest, VoteOption,
+    };
+    use prost::Message;
+
+    use super::{GovWithAppAccess, ProposalOptions};
+    use crate::{Account, NeutronTestApp};
+
+    #[test]
+    fn gov_proposal_lifecycle() {
+        let app = NeutronTestApp::new();
+        let proposer = app
+            .init_account(&[Coin::new(100_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let gov = GovWithAppAccess::new(&app);
+
+        // passed
+        let outcome = gov
+            .run_proposal(
+                vec![],
+                VoteOption::Yes,
+                ProposalOptions {
+                    title: "signal".to_string(),
+                    summary: "signal".to_string(),
+                    ..Default::default()
+                },
+                &proposer,
             )
             .unwrap();
+        assert_eq!(outcome.status, ProposalStatus::Passed);
+        assert_eq!(outcome.failed_reason, "");
 
-        // increase time to pass voting period
-        // self.app.increase_time(voting_period.seconds as u64 + 1);
+        // expedited proposals only wait for the expedited voting period
+        let start = app.get_block_time_seconds();
+        let outcome = gov
+            .run_proposal(
+                vec![],
+                VoteOption::Yes,
+                ProposalOptions {
+                    title: "expedited".to_string(),
+                    summary: "expedited".to_string(),
+                    expedited: true,
+                    ..Default::default()
+                },
+                &proposer,
+            )
+            .unwrap();
+        assert_eq!(outcome.status, ProposalStatus::Passed);
+        let expedited_voting_period = gov.params().unwrap().expedited_voting_period.unwrap();
+        assert!(app.get_block_time_seconds() - start > expedited_voting_period.seconds);
 
-        Ok(submit_proposal_res)
+        // vetoed
+        let outcome = gov
+            .run_proposal(
+                vec![],
+                VoteOption::NoWithVeto,
+                ProposalOptions {
+                    title: "veto".to_string(),
+                    summary: "veto".to_string(),
+                    ..Default::default()
+                },
+                &proposer,
+            )
+            .unwrap();
+        assert_eq!(outcome.status, ProposalStatus::Rejected);
+
+        // rejected without veto
+        let outcome = gov
+            .run_proposal(
+                vec![],
+                VoteOption::No,
+                ProposalOptions {
+                    title: "no".to_string(),
+                    summary: "no".to_string(),
+                    ..Default::default()
+                },
+                &proposer,
+            )
+            .unwrap();
+        assert_eq!(outcome.status, ProposalStatus::Rejected);
+        let tally = outcome.final_tally_result.unwrap();
+        assert_eq!(tally.yes_count, "0");
+        assert_ne!(tally.no_count, "0");
+        assert_eq!(tally.no_with_veto_count, "0");
+
+        // passed, but the message fails when executed
+        let gov_module_address = app.get_module_address("gov").unwrap();
+        let outcome = gov
+            .run_proposal(
+                vec![Any {
+                    type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                    value: MsgSend {
+                        from_address: gov_module_address,
+                        to_address: proposer.address(),
+                        amount: vec![BaseCoin {
+                            denom: "untrn".to_string(),
+                            amount: 1_000_000_000_000_000_000u128.to_string(),
+                        }],
+                    }
+                    .encode_to_vec(),
+                }],
+                VoteOption::Yes,
+                ProposalOptions {
+                    title: "failing".to_string(),
+                    summary: "failing".to_string(),
+                    ..Default::default()
+                },
+                &proposer,
+            )
+            .unwrap();
+        assert_eq!(outcome.status, ProposalStatus::Failed);
+        assert!(outcome.failed_reason.contains("insufficient funds"));
+    }
+
+    #[test]
+    fn gov_tops_up_a_partial_deposit() {
+        let app = NeutronTestApp::new();
+        let proposer = app
+            .init_account(&[Coin::new(100_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let gov = GovWithAppAccess::new(&app);
+
+        // deposit half of the minimum up front, the rest is topped up before voting
+        let min_deposit = gov.params().unwrap().min_deposit;
+        let initial_deposit = min_deposit
+            .iter()
+            .map(|coin| BaseCoin {
+                denom: coin.denom.clone(),
+                amount: (coin.amount.parse::<u128>().unwrap() / 2).to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert!(initial_deposit.iter().all(|coin| coin.amount != "0"));
+
+        let proposal_id = gov
+            .submit_with_deposit(
+                vec![],
+                &ProposalOptions {
+                    title: "partial deposit".to_string(),
+                    summary: "partial deposit".to_string(),
+                    initial_deposit: Some(initial_deposit),
+                    ..Default::default()
+                },
+                proposer.address(),
+                &proposer,
+            )
+            .unwrap()
+            .data
+            .proposal_id;
+
+        // the top-up completes the deposit, which starts the voting period
+        assert_eq!(
+            gov.proposal_status(proposal_id).unwrap(),
+            ProposalStatus::VotingPeriod
+        );
+        let deposits = gov
+            .to_gov()
+            .query_deposits(&QueryDepositsRequest {
+                proposal_id,
+                pagination: None,
+            })
+            .unwrap()
+            .deposits;
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].depositor, proposer.address());
+        assert_eq!(deposits[0].amount, min_deposit);
+
+        let outcome = gov.vote_and_wait(proposal_id, VoteOption::Yes).unwrap();
+        assert_eq!(outcome.status, ProposalStatus::Passed);
     }
 }
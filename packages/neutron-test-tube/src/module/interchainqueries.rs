@@ -0,0 +1,258 @@
+use margined_neutron_std::types::neutron::interchainqueries::{
+    MsgRegisterInterchainQuery, MsgRegisterInterchainQueryResponse,
+    MsgRemoveInterchainQueryRequest, MsgRemoveInterchainQueryResponse,
+    MsgUpdateInterchainQueryRequest, MsgUpdateInterchainQueryResponse, QueryLastRemoteHeight,
+    QueryLastRemoteHeightResponse, QueryParamsRequest, QueryParamsResponse,
+    QueryRegisteredQueriesRequest, QueryRegisteredQueriesResponse, QueryRegisteredQueryRequest,
+    QueryRegisteredQueryResponse, QueryRegisteredQueryResultRequest,
+    QueryRegisteredQueryResultResponse, QueryResult, StorageValue,
+};
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::NeutronTestApp;
+
+pub struct InterchainQueries<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for InterchainQueries<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> InterchainQueries<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub register_interchain_query: MsgRegisterInterchainQuery["/neutron.interchainqueries.MsgRegisterInterchainQuery"] => MsgRegisterInterchainQueryResponse
+    }
+
+    fn_execute! {
+        pub update_interchain_query: MsgUpdateInterchainQueryRequest["/neutron.interchainqueries.MsgUpdateInterchainQueryRequest"] => MsgUpdateInterchainQueryResponse
+    }
+
+    fn_execute! {
+        pub remove_interchain_query: MsgRemoveInterchainQueryRequest["/neutron.interchainqueries.MsgRemoveInterchainQueryRequest"] => MsgRemoveInterchainQueryResponse
+    }
+
+    fn_query! {
+        pub query_params ["/neutron.interchainqueries.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_registered_queries ["/neutron.interchainqueries.Query/RegisteredQueries"]: QueryRegisteredQueriesRequest => QueryRegisteredQueriesResponse
+    }
+
+    fn_query! {
+        pub query_registered_query ["/neutron.interchainqueries.Query/RegisteredQuery"]: QueryRegisteredQueryRequest => QueryRegisteredQueryResponse
+    }
+
+    fn_query! {
+        pub query_result ["/neutron.interchainqueries.Query/QueryResult"]: QueryRegisteredQueryResultRequest => QueryRegisteredQueryResultResponse
+    }
+
+    fn_query! {
+        pub query_last_remote_height ["/neutron.interchainqueries.Query/LastRemoteHeight"]: QueryLastRemoteHeight => QueryLastRemoteHeightResponse
+    }
+}
+
+/// Mock relayer: results are written straight into the keeper, without proofs, at the
+/// current block height as the remote height.
+impl<'a> InterchainQueries<'a, NeutronTestApp> {
+    /// Submit the result of a registered KV query. The owner's `kv_query_result` sudo
+    /// handler is called when `allow_kv_callbacks` is set.
+    pub fn submit_kv_result(
+        &self,
+        query_id: u64,
+        kv_results: Vec<StorageValue>,
+        allow_kv_callbacks: bool,
+    ) -> RunnerResult<()> {
+        self.runner.submit_kv_query_result(
+            query_id,
+            &QueryResult {
+                kv_results,
+                block: None,
+                height: self.runner.get_block_height() as u64,
+                revision: 0,
+                allow_kv_callbacks,
+            },
+        )
+    }
+
+    /// Submit a remote transaction matching a registered TX query, which is delivered to
+    /// the owner's `tx_query_result` sudo handler.
+    pub fn submit_tx_result(&self, query_id: u64, data: &[u8]) -> RunnerResult<()> {
+        self.runner
+            .submit_tx_query_result(query_id, 0, self.runner.get_block_height() as u64, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Binary, Coin};
+    use margined_neutron_std::types::neutron::interchainqueries::{
+        KvKey, MsgRegisterInterchainQuery, QueryRegisteredQueriesRequest,
+        QueryRegisteredQueryResultRequest, StorageValue,
+    };
+    use neutron_sdk::bindings::msg::NeutronMsg;
+    use neutron_sdk::bindings::types::KVKey;
+    use neutron_sdk::sudo::msg::SudoMsg;
+
+    use crate::sudo_recorder::SudoRecorder;
+    use crate::{Account, InterchainQueries, NeutronTestApp};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn interchain_queries_mock_relayer() {
+        let app = NeutronTestApp::new();
+        let icq = InterchainQueries::new(&app);
+
+        let user = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let params = icq.query_params(&Default::default()).unwrap().params;
+        assert!(params.is_some());
+
+        // queries can only be registered by contracts
+        let err = icq
+            .register_interchain_query(
+                MsgRegisterInterchainQuery {
+                    query_type: "kv".to_string(),
+                    keys: vec![KvKey {
+                        path: "bank".to_string(),
+                        key: b"balances".to_vec(),
+                    }],
+                    transactions_filter: String::new(),
+                    connection_id: "connection-0".to_string(),
+                    update_period: 10,
+                    sender: user.address(),
+                },
+                &user,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("is not a contract address"));
+
+        let registered = icq
+            .query_registered_queries(&QueryRegisteredQueriesRequest {
+                owners: vec![],
+                connection_id: String::new(),
+                pagination: None,
+            })
+            .unwrap()
+            .registered_queries;
+        assert!(registered.is_empty());
+
+        // the relayer refuses results for unknown queries
+        let kv_results = vec![StorageValue {
+            storage_prefix: "bank".to_string(),
+            key: b"balances".to_vec(),
+            value: vec![],
+            proof: None,
+        }];
+        assert!(icq.submit_kv_result(1, kv_results, false).is_err());
+        assert!(icq.submit_tx_result(1, b"tx").is_err());
+    }
+
+    #[test]
+    fn interchain_queries_sudo_callbacks() {
+        let app = NeutronTestApp::new();
+        let icq = InterchainQueries::new(&app);
+
+        let owner = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+
+        // the contract pays the query deposits
+        let recorder = SudoRecorder::new(&app, &[Coin::new(10_000_000u128, "untrn")], &owner);
+        recorder
+            .dispatch(
+                vec![
+                    NeutronMsg::RegisterInterchainQuery {
+                        query_type: "kv".to_string(),
+                        keys: vec![KVKey {
+                            path: "bank".to_string(),
+                            key: Binary::from(b"balances".to_vec()),
+                        }],
+                        transactions_filter: String::new(),
+                        connection_id: connection_id.clone(),
+                        update_period: 10,
+                    }
+                    .into(),
+                    NeutronMsg::RegisterInterchainQuery {
+                        query_type: "tx".to_string(),
+                        keys: vec![],
+                        transactions_filter:
+                            r#"[{"field":"transfer.recipient","op":"Eq","value":"cosmos1recipient"}]"#
+                                .to_string(),
+                        connection_id,
+                        update_period: 10,
+                    }
+                    .into(),
+                ],
+                &owner,
+            )
+            .unwrap();
+
+        let registered = icq
+            .query_registered_queries(&QueryRegisteredQueriesRequest {
+                owners: vec![recorder.address.clone()],
+                connection_id: String::new(),
+                pagination: None,
+            })
+            .unwrap()
+            .registered_queries;
+        assert_eq!(registered.len(), 2);
+        let (kv_query, tx_query) = (registered[0].id, registered[1].id);
+        assert_eq!(registered[0].query_type, "kv");
+        assert_eq!(registered[1].query_type, "tx");
+
+        // KV results are stored, and delivered to the owner when callbacks are allowed
+        let kv_results = vec![StorageValue {
+            storage_prefix: "bank".to_string(),
+            key: b"balances".to_vec(),
+            value: b"1000".to_vec(),
+            proof: None,
+        }];
+        icq.submit_kv_result(kv_query, kv_results.clone(), false)
+            .unwrap();
+        assert!(recorder.sudo_calls().is_empty());
+
+        icq.submit_kv_result(kv_query, kv_results.clone(), true)
+            .unwrap();
+        assert_eq!(
+            recorder.sudo_calls(),
+            vec![SudoMsg::KVQueryResult { query_id: kv_query }]
+        );
+
+        let result = icq
+            .query_result(&QueryRegisteredQueryResultRequest { query_id: kv_query })
+            .unwrap()
+            .result
+            .unwrap();
+        assert_eq!(result.kv_results, kv_results);
+
+        // TX results are only delivered through the sudo callback
+        icq.submit_tx_result(tx_query, b"remote tx").unwrap();
+        match recorder.sudo_calls().last().unwrap() {
+            SudoMsg::TxQueryResult { query_id, data, .. } => {
+                assert_eq!(*query_id, tx_query);
+                assert_eq!(data.as_slice(), b"remote tx");
+            }
+            call => panic!("unexpected sudo call {:?}", call),
+        }
+
+        // results are rejected when the callback fails, nothing is recorded by contractmanager
+        recorder.set_sudo_failure(true, &owner);
+        assert!(icq.submit_kv_result(kv_query, kv_results, true).is_err());
+        assert!(icq.submit_tx_result(tx_query, b"remote tx").is_err());
+        assert_eq!(recorder.sudo_calls().len(), 2);
+    }
+}
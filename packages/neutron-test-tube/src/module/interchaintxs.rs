@@ -0,0 +1,312 @@
+use cosmwasm_std::Event;
+use margined_neutron_std::types::neutron::interchaintxs::v1::{
+    MsgRegisterInterchainAccount, MsgRegisterInterchainAccountResponse, MsgSubmitTx,
+    MsgSubmitTxResponse, QueryInterchainAccountAddressRequest,
+    QueryInterchainAccountAddressResponse, QueryParamsRequest, QueryParamsResponse,
+};
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::{IbcPacket, NeutronTestApp, PacketOutcome};
+
+pub struct InterchainTxs<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for InterchainTxs<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> InterchainTxs<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub register_interchain_account: MsgRegisterInterchainAccount["/neutron.interchaintxs.v1.MsgRegisterInterchainAccount"] => MsgRegisterInterchainAccountResponse
+    }
+
+    fn_execute! {
+        pub submit_tx: MsgSubmitTx["/neutron.interchaintxs.v1.MsgSubmitTx"] => MsgSubmitTxResponse
+    }
+
+    fn_query! {
+        pub query_params ["/neutron.interchaintxs.v1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_interchain_account_address ["/neutron.interchaintxs.v1.Query/InterchainAccountAddress"]: QueryInterchainAccountAddressRequest => QueryInterchainAccountAddressResponse
+    }
+}
+
+/// Simulated host side of interchain accounts registered on a
+/// [mock connection](NeutronTestApp::create_mock_connection).
+impl<'a> InterchainTxs<'a, NeutronTestApp> {
+    /// Port of the interchain account `interchain_account_id` owned by the contract `owner`.
+    pub fn port_id(owner: &str, interchain_account_id: &str) -> String {
+        format!("icacontroller-{}.{}", owner, interchain_account_id)
+    }
+
+    /// Complete the handshake of a registered interchain account. The owner receives
+    /// the `open_ack` sudo callback.
+    pub fn open_ack(
+        &self,
+        owner: &str,
+        interchain_account_id: &str,
+        channel_id: &str,
+    ) -> RunnerResult<()> {
+        self.runner
+            .open_ack_channel(&Self::port_id(owner, interchain_account_id), channel_id)
+    }
+
+    /// Answer every packet sent in `events` (e.g. the events of the tx that called
+    /// `MsgSubmitTx`) with `outcome`. The owner receives the matching `response`, `error`
+    /// or `timeout` sudo callback.
+    pub fn relay(
+        &self,
+        events: &[Event],
+        outcome: &PacketOutcome,
+        relayer: &str,
+    ) -> RunnerResult<Vec<IbcPacket>> {
+        let packets: Vec<IbcPacket> = IbcPacket::from_events(events)?
+            .into_iter()
+            .filter(|p| p.source_port.starts_with("icacontroller-"))
+            .collect();
+
+        for packet in &packets {
+            self.runner.relay_packet(packet, outcome, relayer)?;
+        }
+
+        Ok(packets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmrs::proto::cosmos::base::abci::v1beta1::TxMsgData;
+    use cosmwasm_std::{coin, Binary, Coin, Event};
+    use margined_neutron_std::types::neutron::interchaintxs::v1::{
+        MsgRegisterInterchainAccount, QueryInterchainAccountAddressRequest, QueryParamsRequest,
+    };
+    use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
+    use neutron_sdk::bindings::types::ProtobufAny;
+    use neutron_sdk::sudo::msg::SudoMsg;
+    use prost::Message;
+
+    use crate::sudo_recorder::SudoRecorder;
+    use crate::{Account, IbcHeight, IbcPacket, InterchainTxs, NeutronTestApp, PacketOutcome};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn interchain_txs_mock_host() {
+        let app = NeutronTestApp::new();
+        let ica = InterchainTxs::new(&app);
+
+        let user = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        assert!(ica
+            .query_params(&QueryParamsRequest {})
+            .unwrap()
+            .params
+            .is_some());
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        assert!(connection_id.starts_with("connection-"));
+
+        // interchain accounts can only be registered by contracts
+        let err = ica
+            .register_interchain_account(
+                MsgRegisterInterchainAccount {
+                    from_address: user.address(),
+                    connection_id,
+                    interchain_account_id: "test".to_string(),
+                    register_fee: vec![],
+                    ordering: 0,
+                },
+                &user,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("is not a contract address"));
+
+        // packets are parsed from the `send_packet` events
+        let event = Event::new("send_packet").add_attributes(vec![
+            ("packet_sequence", "1"),
+            ("packet_src_port", "icacontroller-owner.test"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_dst_port", "icahost"),
+            ("packet_dst_channel", "channel-0"),
+            ("packet_data_hex", "7b7d"),
+            ("packet_timeout_height", "0-0"),
+            ("packet_timeout_timestamp", "1000"),
+        ]);
+        let packets = IbcPacket::from_events(&[event.clone()]).unwrap();
+        assert_eq!(
+            packets,
+            vec![IbcPacket {
+                sequence: 1,
+                source_port: "icacontroller-owner.test".to_string(),
+                source_channel: "channel-0".to_string(),
+                destination_port: "icahost".to_string(),
+                destination_channel: "channel-0".to_string(),
+                data: Binary::from(b"{}".to_vec()),
+                timeout_height: IbcHeight::default(),
+                timeout_timestamp: 1000,
+            }]
+        );
+
+        // the mock host refuses packets that were never sent
+        for outcome in [
+            PacketOutcome::ica_success(vec![]),
+            PacketOutcome::Error("failed".to_string()),
+            PacketOutcome::Timeout,
+        ] {
+            assert!(ica
+                .relay(&[event.clone()], &outcome, &user.address())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn interchain_txs_contract_callbacks() {
+        let app = NeutronTestApp::new();
+        let ica = InterchainTxs::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (owner, relayer) = (&accs[0], &accs[1]);
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+
+        // the contract pays the registration and IBC fees
+        let recorder = SudoRecorder::new(&app, &[Coin::new(10_000_000u128, "untrn")], owner);
+        let res = recorder
+            .dispatch(
+                vec![NeutronMsg::RegisterInterchainAccount {
+                    connection_id: connection_id.clone(),
+                    interchain_account_id: "test".to_string(),
+                    register_fee: Some(vec![coin(1_000_000, "untrn")]),
+                    ordering: None,
+                }
+                .into()],
+                owner,
+            )
+            .unwrap();
+        let channel_id = res
+            .events
+            .iter()
+            .find(|e| e.ty == "channel_open_init")
+            .and_then(|e| e.attributes.iter().find(|a| a.key == "channel_id"))
+            .unwrap()
+            .value
+            .clone();
+
+        ica.open_ack(&recorder.address, "test", &channel_id)
+            .unwrap();
+        let port_id = InterchainTxs::port_id(&recorder.address, "test");
+        match &recorder.sudo_calls()[..] {
+            [SudoMsg::OpenAck {
+                port_id: acked_port,
+                channel_id: acked_channel,
+                ..
+            }] => {
+                assert_eq!(acked_port, &port_id);
+                assert_eq!(acked_channel, &channel_id);
+            }
+            calls => panic!("unexpected sudo calls {:?}", calls),
+        }
+
+        let ica_address = ica
+            .query_interchain_account_address(&QueryInterchainAccountAddressRequest {
+                owner_address: recorder.address.clone(),
+                interchain_account_id: "test".to_string(),
+                connection_id: connection_id.clone(),
+            })
+            .unwrap()
+            .interchain_account_address;
+        assert!(!ica_address.is_empty());
+
+        let submit_tx = || {
+            recorder
+                .dispatch(
+                    vec![NeutronMsg::SubmitTx {
+                        connection_id: connection_id.clone(),
+                        interchain_account_id: "test".to_string(),
+                        msgs: vec![ProtobufAny {
+                            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                            value: Binary::default(),
+                        }],
+                        memo: String::new(),
+                        timeout: 3600,
+                        fee: IbcFee {
+                            recv_fee: vec![],
+                            ack_fee: vec![coin(1_000, "untrn")],
+                            timeout_fee: vec![coin(1_000, "untrn")],
+                        },
+                    }
+                    .into()],
+                    owner,
+                )
+                .unwrap()
+        };
+
+        // success ack, with the responses of the host
+        let res = submit_tx();
+        let msg_responses = vec![cosmrs::Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSendResponse".to_string(),
+            value: vec![],
+        }];
+        let packets = ica
+            .relay(
+                &res.events,
+                &PacketOutcome::ica_success(msg_responses.clone()),
+                &relayer.address(),
+            )
+            .unwrap();
+        assert_eq!(packets.len(), 1);
+        match recorder.sudo_calls().last().unwrap() {
+            SudoMsg::Response { request, data } => {
+                assert_eq!(request.sequence, Some(packets[0].sequence));
+                assert_eq!(request.source_channel.as_deref(), Some(channel_id.as_str()));
+                let data = TxMsgData::decode(data.as_slice()).unwrap();
+                assert_eq!(data.msg_responses, msg_responses);
+            }
+            call => panic!("unexpected sudo call {:?}", call),
+        }
+
+        // error ack
+        let res = submit_tx();
+        let packets = ica
+            .relay(
+                &res.events,
+                &PacketOutcome::Error("failed".to_string()),
+                &relayer.address(),
+            )
+            .unwrap();
+        match recorder.sudo_calls().last().unwrap() {
+            SudoMsg::Error { request, details } => {
+                assert_eq!(request.sequence, Some(packets[0].sequence));
+                assert_eq!(details, "failed");
+            }
+            call => panic!("unexpected sudo call {:?}", call),
+        }
+
+        // timeout, last as it closes an ordered channel
+        let res = submit_tx();
+        let packets = ica
+            .relay(&res.events, &PacketOutcome::Timeout, &relayer.address())
+            .unwrap();
+        match recorder.sudo_calls().last().unwrap() {
+            SudoMsg::Timeout { request } => {
+                assert_eq!(request.sequence, Some(packets[0].sequence));
+            }
+            call => panic!("unexpected sudo call {:?}", call),
+        }
+        assert_eq!(recorder.sudo_calls().len(), 4);
+    }
+}
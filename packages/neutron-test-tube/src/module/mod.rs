@@ -1,21 +1,49 @@
 mod adminmodule;
+mod auth;
 mod authz;
 mod bank;
+mod contractmanager;
+mod cron;
 mod dex;
+mod distribution;
+mod feeburner;
+mod feegrant;
+mod feerefunder;
 mod gov;
+mod interchainqueries;
+mod interchaintxs;
 mod slinky;
+mod staking;
 mod tokenfactory;
+mod transfer;
+mod upgrade;
 mod wasm;
 
 pub use test_tube_ntrn::macros;
 pub use test_tube_ntrn::module::Module;
 
-pub use adminmodule::Admin;
-pub use authz::Authz;
+pub use adminmodule::{Admin, AdminExecuteResponse};
+pub use auth::Auth;
+pub use authz::{Authorization, Authz, AuthzExecResponse};
 pub use bank::Bank;
-pub use dex::Dex;
+pub use contractmanager::ContractManager;
+pub use cron::{Cron, ScheduleExecution};
+pub use dex::{
+    pair_id, price_from_tick, tick_from_price, Dex, ExpiredLimitOrder, LimitOrder, OrderBookSide,
+    OrderBookSnapshot, PoolLiquidity, PrecDec, TrancheLiquidity, MAX_TICK_INDEX,
+};
+pub use distribution::Distribution;
+pub use feeburner::{FeeBurner, FeeSplit};
+pub use feegrant::{FeeAllowance, FeeGrant};
+pub use feerefunder::FeeRefunder;
 pub use gov::Gov;
 pub use gov::GovWithAppAccess;
+pub use gov::{ProposalOptions, ProposalOutcome};
+pub use interchainqueries::InterchainQueries;
+pub use interchaintxs::InterchainTxs;
 pub use slinky::Slinky;
+pub use staking::Staking;
 pub use tokenfactory::TokenFactory;
+pub use transfer::{Transfer, TRANSFER_PORT, TRANSFER_VERSION};
+pub use upgrade::{Upgrade, UpgradeHalt};
 pub use wasm::Wasm;
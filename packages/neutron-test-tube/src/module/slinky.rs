@@ -1,11 +1,14 @@
 use margined_neutron_std::types::slinky::{
     marketmap::v1 as MarketMapTypesV1, oracle::v1 as OracleTypesV1,
 };
-use test_tube_ntrn::{fn_execute, fn_query};
+use test_tube_ntrn::runner::app::SlinkyPrices;
+use test_tube_ntrn::{fn_execute, fn_query, Account, RunnerError, RunnerExecuteResult};
 
 use test_tube_ntrn::module::Module;
 use test_tube_ntrn::runner::Runner;
 
+use crate::NeutronTestApp;
+
 pub struct Slinky<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -24,6 +27,22 @@ where
         pub create_markets: MarketMapTypesV1::MsgCreateMarkets["/slinky.marketmap.v1.MsgCreateMarkets"] => MarketMapTypesV1::MsgCreateMarketsResponse
     }
 
+    fn_execute! {
+        pub update_markets: MarketMapTypesV1::MsgUpdateMarkets["/slinky.marketmap.v1.MsgUpdateMarkets"] => MarketMapTypesV1::MsgUpdateMarketsResponse
+    }
+
+    fn_execute! {
+        pub upsert_markets: MarketMapTypesV1::MsgUpsertMarkets["/slinky.marketmap.v1.MsgUpsertMarkets"] => MarketMapTypesV1::MsgUpsertMarketsResponse
+    }
+
+    fn_execute! {
+        pub remove_markets: MarketMapTypesV1::MsgRemoveMarkets["/slinky.marketmap.v1.MsgRemoveMarkets"] => MarketMapTypesV1::MsgRemoveMarketsResponse
+    }
+
+    fn_execute! {
+        pub update_params: MarketMapTypesV1::MsgParams["/slinky.marketmap.v1.MsgParams"] => MarketMapTypesV1::MsgParamsResponse
+    }
+
     fn_query! {
         pub get_all_currency_pairs ["/slinky.oracle.v1.Query/GetAllCurrencyPairs"]: OracleTypesV1::GetAllCurrencyPairsRequest => OracleTypesV1::GetAllCurrencyPairsResponse
     }
@@ -43,6 +62,56 @@ where
     fn_query! {
         pub get_params ["/slinky.marketmap.v1.Query/Params"]: MarketMapTypesV1::ParamsRequest => MarketMapTypesV1::ParamsResponse
     }
+
+    fn_query! {
+        pub get_market_map ["/slinky.marketmap.v1.Query/MarketMap"]: MarketMapTypesV1::MarketMapRequest => MarketMapTypesV1::MarketMapResponse
+    }
+
+    fn_query! {
+        pub get_market ["/slinky.marketmap.v1.Query/Market"]: MarketMapTypesV1::MarketRequest => MarketMapTypesV1::MarketResponse
+    }
+
+    fn_query! {
+        pub get_last_updated ["/slinky.marketmap.v1.Query/LastUpdated"]: MarketMapTypesV1::LastUpdatedRequest => MarketMapTypesV1::LastUpdatedResponse
+    }
+}
+
+impl<'a> Slinky<'a, NeutronTestApp> {
+    /// Create a market signed by the genesis market authority (the first validator)
+    /// and seed its first oracle price in the following block.
+    pub fn create_market_with_price(
+        &self,
+        market: MarketMapTypesV1::Market,
+        price: u128,
+    ) -> RunnerExecuteResult<MarketMapTypesV1::MsgCreateMarketsResponse> {
+        let currency_pair = market
+            .ticker
+            .as_ref()
+            .and_then(|ticker| ticker.currency_pair.clone())
+            .ok_or_else(|| {
+                RunnerError::GenericError("market ticker has no currency pair".to_string())
+            })?;
+
+        let authority = self
+            .runner
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)?;
+
+        let res = self.create_markets(
+            MarketMapTypesV1::MsgCreateMarkets {
+                authority: authority.address(),
+                create_markets: vec![market],
+            },
+            &authority,
+        )?;
+
+        self.runner.set_slinky_prices(&[SlinkyPrices {
+            base: currency_pair.base,
+            quote: currency_pair.quote,
+            price,
+        }]);
+
+        Ok(res)
+    }
 }
 
 #[cfg(test)]
@@ -57,8 +126,43 @@ mod tests {
     };
 
     use crate::{Account, NeutronTestApp, Slinky};
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::types::slinky::marketmap::v1::{
+        self as MarketMapTypes, LastUpdatedRequest, MarketMapRequest, MarketRequest, MsgParams,
+        MsgRemoveMarkets, MsgUpdateMarkets, MsgUpsertMarkets, ParamsRequest,
+    };
     use test_tube_ntrn::{runner::app::SlinkyPrices, Module};
 
+    fn ntrn_usdc_market(enabled: bool) -> Market {
+        Market {
+            ticker: Some(Ticker {
+                currency_pair: Some(CurrencyPair {
+                    base: "NTRN".to_string(),
+                    quote: "USDC".to_string(),
+                }),
+                decimals: 6,
+                min_provider_count: 1,
+                enabled,
+                metadata_json: "".to_string(),
+            }),
+            provider_configs: vec![ProviderConfig {
+                name: "margined".to_string(),
+                off_chain_ticker: "NRTN/USD".to_string(),
+                normalize_by_pair: None,
+                invert: false,
+                metadata_json: "".to_string(),
+            }],
+        }
+    }
+
+    fn market_map(slinky: &Slinky<NeutronTestApp>) -> MarketMapTypes::MarketMap {
+        slinky
+            .get_market_map(&MarketMapRequest {})
+            .unwrap()
+            .market_map
+            .unwrap()
+    }
+
     #[test]
     fn slinky_integration() {
         let app = NeutronTestApp::new();
@@ -246,4 +350,200 @@ mod tests {
             .unwrap();
         assert_eq!(res.price.unwrap().price, "5012345".to_string());
     }
+
+    #[test]
+    fn slinky_create_market_with_price() {
+        let app = NeutronTestApp::new();
+
+        let slinky = Slinky::new(&app);
+
+        let ntrn_usdc = CurrencyPair {
+            base: "NTRN".to_string(),
+            quote: "USDC".to_string(),
+        };
+
+        slinky
+            .create_market_with_price(ntrn_usdc_market(true), 4230000u128)
+            .unwrap();
+
+        let res = slinky
+            .get_price(&OracleTypes::GetPriceRequest {
+                currency_pair: Some(ntrn_usdc.clone()),
+            })
+            .unwrap();
+        assert_eq!(res.price.unwrap().price, "4230000".to_string());
+        assert_eq!(res.decimals, 6);
+
+        let market = slinky
+            .get_market(&MarketRequest {
+                currency_pair: Some(ntrn_usdc.clone()),
+            })
+            .unwrap()
+            .market
+            .unwrap();
+        assert_eq!(market, ntrn_usdc_market(true));
+
+        let last_updated = slinky
+            .get_last_updated(&LastUpdatedRequest {})
+            .unwrap()
+            .last_updated;
+
+        // disable the market again
+        let val = app
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)
+            .unwrap();
+        slinky
+            .update_markets(
+                MsgUpdateMarkets {
+                    authority: val.address(),
+                    update_markets: vec![ntrn_usdc_market(false)],
+                },
+                &val,
+            )
+            .unwrap();
+
+        let market = slinky
+            .get_market(&MarketRequest {
+                currency_pair: Some(ntrn_usdc),
+            })
+            .unwrap()
+            .market
+            .unwrap();
+        assert!(!market.ticker.unwrap().enabled);
+
+        assert!(
+            slinky
+                .get_last_updated(&LastUpdatedRequest {})
+                .unwrap()
+                .last_updated
+                > last_updated
+        );
+    }
+
+    #[test]
+    fn slinky_upsert_and_remove_markets() {
+        let app = NeutronTestApp::new();
+
+        let slinky = Slinky::new(&app);
+
+        let val = app
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)
+            .unwrap();
+
+        assert!(!market_map(&slinky).markets.contains_key("NTRN/USDC"));
+
+        // upsert creates a missing market
+        slinky
+            .upsert_markets(
+                MsgUpsertMarkets {
+                    authority: val.address(),
+                    markets: vec![ntrn_usdc_market(true)],
+                },
+                &val,
+            )
+            .unwrap();
+        assert_eq!(
+            market_map(&slinky).markets.get("NTRN/USDC"),
+            Some(&ntrn_usdc_market(true))
+        );
+
+        // enabled markets cannot be removed
+        let remove = MsgRemoveMarkets {
+            authority: val.address(),
+            markets: vec!["NTRN/USDC".to_string()],
+        };
+        assert!(slinky.remove_markets(remove.clone(), &val).is_err());
+        assert!(market_map(&slinky).markets.contains_key("NTRN/USDC"));
+
+        // upsert updates an existing market
+        let mut updated = ntrn_usdc_market(false);
+        updated.ticker.as_mut().unwrap().decimals = 8;
+        slinky
+            .upsert_markets(
+                MsgUpsertMarkets {
+                    authority: val.address(),
+                    markets: vec![updated.clone()],
+                },
+                &val,
+            )
+            .unwrap();
+        assert_eq!(market_map(&slinky).markets.get("NTRN/USDC"), Some(&updated));
+
+        let res = slinky.remove_markets(remove, &val).unwrap();
+        assert_eq!(res.data.deleted_markets, vec!["NTRN/USDC".to_string()]);
+        assert!(!market_map(&slinky).markets.contains_key("NTRN/USDC"));
+    }
+
+    #[test]
+    fn slinky_update_params() {
+        let app = NeutronTestApp::new();
+
+        let slinky = Slinky::new(&app);
+
+        let val = app
+            .get_first_validator_signing_account("untrn".to_string(), 1.3)
+            .unwrap();
+        let new_authority = app
+            .init_account(&[Coin::new(1_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let params = slinky
+            .get_params(&ParamsRequest {})
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(params.market_authorities, vec![val.address()]);
+
+        let new_params = MarketMapTypes::Params {
+            market_authorities: vec![new_authority.address()],
+            admin: params.admin.clone(),
+        };
+
+        // only the module authority (the admin module) can update the params
+        assert!(slinky
+            .update_params(
+                MsgParams {
+                    params: Some(new_params.clone()),
+                    authority: val.address(),
+                },
+                &val,
+            )
+            .is_err());
+        assert_eq!(
+            slinky.get_params(&ParamsRequest {}).unwrap().params,
+            Some(params)
+        );
+
+        app.update_params(new_params.clone()).unwrap();
+        assert_eq!(
+            slinky.get_params(&ParamsRequest {}).unwrap().params,
+            Some(new_params)
+        );
+
+        // the validator lost its market authority
+        assert!(slinky
+            .upsert_markets(
+                MsgUpsertMarkets {
+                    authority: val.address(),
+                    markets: vec![ntrn_usdc_market(true)],
+                },
+                &val,
+            )
+            .is_err());
+        assert!(!market_map(&slinky).markets.contains_key("NTRN/USDC"));
+
+        slinky
+            .upsert_markets(
+                MsgUpsertMarkets {
+                    authority: new_authority.address(),
+                    markets: vec![ntrn_usdc_market(true)],
+                },
+                &new_authority,
+            )
+            .unwrap();
+        assert_eq!(
+            market_map(&slinky).markets.get("NTRN/USDC"),
+            Some(&ntrn_usdc_market(true))
+        );
+    }
 }
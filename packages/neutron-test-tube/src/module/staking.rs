@@ -0,0 +1,193 @@
+use margined_neutron_std::types::cosmos::staking::v1beta1::{
+    MsgBeginRedelegate, MsgBeginRedelegateResponse, MsgCancelUnbondingDelegation,
+    MsgCancelUnbondingDelegationResponse, MsgDelegate, MsgDelegateResponse, MsgUndelegate,
+    MsgUndelegateResponse, QueryDelegationRequest, QueryDelegationResponse,
+    QueryDelegatorDelegationsRequest, QueryDelegatorDelegationsResponse,
+    QueryDelegatorUnbondingDelegationsRequest, QueryDelegatorUnbondingDelegationsResponse,
+    QueryParamsRequest, QueryParamsResponse, QueryPoolRequest, QueryPoolResponse,
+    QueryRedelegationsRequest, QueryRedelegationsResponse, QueryUnbondingDelegationRequest,
+    QueryUnbondingDelegationResponse, QueryValidatorRequest, QueryValidatorResponse,
+    QueryValidatorsRequest, QueryValidatorsResponse, Validator,
+};
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+pub struct Staking<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Staking<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Staking<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub delegate: MsgDelegate["/cosmos.staking.v1beta1.MsgDelegate"] => MsgDelegateResponse
+    }
+
+    fn_execute! {
+        pub undelegate: MsgUndelegate["/cosmos.staking.v1beta1.MsgUndelegate"] => MsgUndelegateResponse
+    }
+
+    fn_execute! {
+        pub begin_redelegate: MsgBeginRedelegate["/cosmos.staking.v1beta1.MsgBeginRedelegate"] => MsgBeginRedelegateResponse
+    }
+
+    fn_execute! {
+        pub cancel_unbonding_delegation: MsgCancelUnbondingDelegation["/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation"] => MsgCancelUnbondingDelegationResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.staking.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_pool ["/cosmos.staking.v1beta1.Query/Pool"]: QueryPoolRequest => QueryPoolResponse
+    }
+
+    fn_query! {
+        pub query_validators ["/cosmos.staking.v1beta1.Query/Validators"]: QueryValidatorsRequest => QueryValidatorsResponse
+    }
+
+    fn_query! {
+        pub query_validator ["/cosmos.staking.v1beta1.Query/Validator"]: QueryValidatorRequest => QueryValidatorResponse
+    }
+
+    fn_query! {
+        pub query_delegation ["/cosmos.staking.v1beta1.Query/Delegation"]: QueryDelegationRequest => QueryDelegationResponse
+    }
+
+    fn_query! {
+        pub query_delegator_delegations ["/cosmos.staking.v1beta1.Query/DelegatorDelegations"]: QueryDelegatorDelegationsRequest => QueryDelegatorDelegationsResponse
+    }
+
+    fn_query! {
+        pub query_unbonding_delegation ["/cosmos.staking.v1beta1.Query/UnbondingDelegation"]: QueryUnbondingDelegationRequest => QueryUnbondingDelegationResponse
+    }
+
+    fn_query! {
+        pub query_delegator_unbonding_delegations ["/cosmos.staking.v1beta1.Query/DelegatorUnbondingDelegations"]: QueryDelegatorUnbondingDelegationsRequest => QueryDelegatorUnbondingDelegationsResponse
+    }
+
+    fn_query! {
+        pub query_redelegations ["/cosmos.staking.v1beta1.Query/Redelegations"]: QueryRedelegationsRequest => QueryRedelegationsResponse
+    }
+
+    /// Validators in the active set, the genesis validator first.
+    pub fn bonded_validators(&self) -> RunnerResult<Vec<Validator>> {
+        Ok(self
+            .query_validators(&QueryValidatorsRequest {
+                status: "BOND_STATUS_BONDED".to_string(),
+                pagination: None,
+            })?
+            .validators)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use margined_neutron_std::types::cosmos::staking::v1beta1::{
+        MsgBeginRedelegate, MsgDelegate, MsgUndelegate, QueryDelegationRequest,
+        QueryUnbondingDelegationRequest,
+    };
+
+    use crate::{Account, NeutronTestApp, Staking};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn staking_delegation_flow() {
+        let app = NeutronTestApp::new();
+        let staking = Staking::new(&app);
+
+        let delegator = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let validator = staking
+            .bonded_validators()
+            .unwrap()
+            .remove(0)
+            .operator_address;
+
+        let untrn = |amount: u128| {
+            Some(BaseCoin {
+                denom: "untrn".to_string(),
+                amount: amount.to_string(),
+            })
+        };
+
+        staking
+            .delegate(
+                MsgDelegate {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                    amount: untrn(1_000_000),
+                },
+                &delegator,
+            )
+            .unwrap();
+
+        let delegated = || {
+            staking
+                .query_delegation(&QueryDelegationRequest {
+                    delegator_addr: delegator.address(),
+                    validator_addr: validator.clone(),
+                })
+                .unwrap()
+                .delegation_response
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+        };
+        assert_eq!(delegated(), "1000000");
+
+        // there is a single validator to redelegate to
+        assert!(staking
+            .begin_redelegate(
+                MsgBeginRedelegate {
+                    delegator_address: delegator.address(),
+                    validator_src_address: validator.clone(),
+                    validator_dst_address: validator.clone(),
+                    amount: untrn(1_000),
+                },
+                &delegator,
+            )
+            .is_err());
+
+        let res = staking
+            .undelegate(
+                MsgUndelegate {
+                    delegator_address: delegator.address(),
+                    validator_address: validator.clone(),
+                    amount: untrn(400_000),
+                },
+                &delegator,
+            )
+            .unwrap();
+        assert!(res.data.completion_time.is_some());
+        assert_eq!(delegated(), "600000");
+
+        let entries = staking
+            .query_unbonding_delegation(&QueryUnbondingDelegationRequest {
+                delegator_addr: delegator.address(),
+                validator_addr: validator,
+            })
+            .unwrap()
+            .unbond
+            .unwrap()
+            .entries;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].balance, "400000");
+    }
+}
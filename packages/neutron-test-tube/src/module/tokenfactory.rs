@@ -1,14 +1,21 @@
+use cosmwasm_std::Uint128;
+use margined_neutron_std::types::cosmos::base::v1beta1::Coin;
 use margined_neutron_std::types::osmosis::tokenfactory::v1beta1::{
     MsgBurn, MsgBurnResponse, MsgChangeAdmin, MsgChangeAdminResponse, MsgCreateDenom,
-    MsgCreateDenomResponse, MsgMint, MsgMintResponse, MsgSetDenomMetadata,
-    MsgSetDenomMetadataResponse, QueryDenomAuthorityMetadataRequest,
+    MsgCreateDenomResponse, MsgForceTransfer, MsgForceTransferResponse, MsgMint, MsgMintResponse,
+    MsgSetBeforeSendHook, MsgSetBeforeSendHookResponse, MsgSetDenomMetadata,
+    MsgSetDenomMetadataResponse, Params, QueryBeforeSendHookAddressRequest,
+    QueryBeforeSendHookAddressResponse, QueryDenomAuthorityMetadataRequest,
     QueryDenomAuthorityMetadataResponse, QueryDenomsFromCreatorRequest,
-    QueryDenomsFromCreatorResponse, QueryParamsRequest, QueryParamsResponse,
+    QueryDenomsFromCreatorResponse, QueryParamsRequest, QueryParamsResponse, WhitelistedHook,
 };
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query, Account, SigningAccount};
 
 use test_tube_ntrn::module::Module;
 use test_tube_ntrn::runner::Runner;
-use test_tube_ntrn::{fn_execute, fn_query};
+
+use crate::NeutronTestApp;
 
 pub struct TokenFactory<'a, R: Runner<'a>> {
     runner: &'a R,
@@ -44,6 +51,14 @@ where
         pub set_denom_metadata: MsgSetDenomMetadata  ["/osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata"]  => MsgSetDenomMetadataResponse
     }
 
+    fn_execute! {
+        pub set_before_send_hook: MsgSetBeforeSendHook ["/osmosis.tokenfactory.v1beta1.MsgSetBeforeSendHook"] => MsgSetBeforeSendHookResponse
+    }
+
+    fn_execute! {
+        pub force_transfer: MsgForceTransfer ["/osmosis.tokenfactory.v1beta1.MsgForceTransfer"] => MsgForceTransferResponse
+    }
+
     fn_query! {
         pub query_params ["/osmosis.tokenfactory.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
     }
@@ -55,6 +70,68 @@ where
     fn_query! {
         pub query_denoms_from_creator ["/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator"]: QueryDenomsFromCreatorRequest => QueryDenomsFromCreatorResponse
     }
+
+    fn_query! {
+        pub query_before_send_hook_address ["/osmosis.tokenfactory.v1beta1.Query/BeforeSendHookAddress"]: QueryBeforeSendHookAddressRequest => QueryBeforeSendHookAddressResponse
+    }
+
+    /// Create `factory/{signer}/{subdenom}` and mint `amount` of it to `to`.
+    /// Returns the full denom.
+    pub fn create_and_mint(
+        &self,
+        subdenom: &str,
+        amount: impl Into<Uint128>,
+        to: &str,
+        signer: &SigningAccount,
+    ) -> RunnerResult<String> {
+        let denom = self
+            .create_denom(
+                MsgCreateDenom {
+                    sender: signer.address(),
+                    subdenom: subdenom.to_string(),
+                },
+                signer,
+            )?
+            .data
+            .new_token_denom;
+
+        self.mint(
+            MsgMint {
+                sender: signer.address(),
+                amount: Some(Coin {
+                    denom: denom.clone(),
+                    amount: amount.into().to_string(),
+                }),
+                mint_to_address: to.to_string(),
+            },
+            signer,
+        )?;
+
+        Ok(denom)
+    }
+}
+
+impl<'a> TokenFactory<'a, NeutronTestApp> {
+    /// Allow contracts of `code_id` to be set as before-send hook of the denoms created by
+    /// `denom_creator`. Neutron only calls hooks whitelisted in the tokenfactory params.
+    pub fn whitelist_before_send_hook(
+        &self,
+        code_id: u64,
+        denom_creator: &str,
+    ) -> RunnerResult<()> {
+        let mut params = self.runner.get_params::<Params>()?;
+
+        let hook = WhitelistedHook {
+            code_id,
+            denom_creator: denom_creator.to_string(),
+        };
+        if params.whitelisted_hooks.contains(&hook) {
+            return Ok(());
+        }
+        params.whitelisted_hooks.push(hook);
+
+        self.runner.update_params(params)
+    }
 }
 
 #[cfg(test)]
@@ -155,4 +232,129 @@ mod tests {
         assert_eq!("0", balance.amount);
         assert_eq!(coin.denom, balance.denom);
     }
+
+    #[test]
+    fn tokenfactory_before_send_hook() {
+        use cw1_whitelist::msg::InstantiateMsg;
+        use margined_neutron_std::types::cosmos::bank::v1beta1::MsgSend;
+        use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+        use margined_neutron_std::types::osmosis::tokenfactory::v1beta1::{
+            MsgForceTransfer, MsgSetBeforeSendHook, QueryBeforeSendHookAddressRequest,
+        };
+
+        use crate::Wasm;
+
+        let app = NeutronTestApp::new();
+        let tokenfactory = TokenFactory::new(&app);
+        let bank = Bank::new(&app);
+        let wasm = Wasm::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(100_000_000_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (creator, holder) = (&accs[0], &accs[1]);
+
+        let balance = |address: String, denom: &str| {
+            bank.query_balance(&QueryBalanceRequest {
+                address,
+                denom: denom.to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap()
+            .amount
+        };
+
+        let denom = tokenfactory
+            .create_and_mint("ucompliance", 1_000u128, &holder.address(), creator)
+            .unwrap();
+        assert_eq!(denom, format!("factory/{}/ucompliance", creator.address()));
+        assert_eq!(balance(holder.address(), &denom), "1000");
+
+        // the denom admin can move tokens of any holder
+        let coin = |amount: &str| BaseCoin {
+            denom: denom.clone(),
+            amount: amount.to_string(),
+        };
+        tokenfactory
+            .force_transfer(
+                MsgForceTransfer {
+                    sender: creator.address(),
+                    amount: Some(coin("100")),
+                    transfer_from_address: holder.address(),
+                    transfer_to_address: creator.address(),
+                },
+                creator,
+            )
+            .unwrap();
+        assert_eq!(balance(holder.address(), &denom), "900");
+        assert_eq!(balance(creator.address(), &denom), "100");
+
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, creator)
+            .unwrap()
+            .data
+            .code_id;
+        let contract = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![creator.address()],
+                    mutable: false,
+                },
+                None,
+                Some("hook"),
+                &[],
+                creator,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let set_hook = |contract_addr: &str| {
+            tokenfactory.set_before_send_hook(
+                MsgSetBeforeSendHook {
+                    sender: creator.address(),
+                    denom: denom.clone(),
+                    contract_addr: contract_addr.to_string(),
+                },
+                creator,
+            )
+        };
+
+        // hooks must be whitelisted first
+        assert!(set_hook(&contract).is_err());
+
+        tokenfactory
+            .whitelist_before_send_hook(code_id, &creator.address())
+            .unwrap();
+        set_hook(&contract).unwrap();
+
+        let hook = tokenfactory
+            .query_before_send_hook_address(&QueryBeforeSendHookAddressRequest {
+                creator: creator.address(),
+                subdenom: "ucompliance".to_string(),
+            })
+            .unwrap()
+            .contract_addr;
+        assert_eq!(hook, contract);
+
+        // cw1-whitelist has no sudo entry point, so the hook blocks every transfer
+        let send = || {
+            bank.send(
+                MsgSend {
+                    from_address: holder.address(),
+                    to_address: creator.address(),
+                    amount: vec![coin("10")],
+                },
+                holder,
+            )
+        };
+        assert!(send().is_err());
+
+        set_hook("").unwrap();
+        send().unwrap();
+        assert_eq!(balance(holder.address(), &denom), "890");
+    }
 }
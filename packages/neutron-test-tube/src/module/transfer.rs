@@ -0,0 +1,457 @@
+use cosmwasm_std::Event;
+use margined_neutron_std::types::ibc::applications::transfer::v1::{
+    MsgTransfer, MsgTransferResponse, QueryDenomHashRequest, QueryDenomHashResponse,
+    QueryDenomTraceRequest, QueryDenomTraceResponse, QueryParamsRequest, QueryParamsResponse,
+};
+use serde_json::{json, Value};
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::{IbcPacket, NeutronTestApp, PacketOutcome};
+
+pub const TRANSFER_PORT: &str = "transfer";
+pub const TRANSFER_VERSION: &str = "ics20-1";
+
+pub struct Transfer<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Transfer<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Transfer<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub transfer: MsgTransfer["/ibc.applications.transfer.v1.MsgTransfer"] => MsgTransferResponse
+    }
+
+    fn_query! {
+        pub query_params ["/ibc.applications.transfer.v1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_denom_trace ["/ibc.applications.transfer.v1.Query/DenomTrace"]: QueryDenomTraceRequest => QueryDenomTraceResponse
+    }
+
+    fn_query! {
+        pub query_denom_hash ["/ibc.applications.transfer.v1.Query/DenomHash"]: QueryDenomHashRequest => QueryDenomHashResponse
+    }
+}
+
+/// Mock counterparty for transfers over a
+/// [mock connection](NeutronTestApp::create_mock_connection).
+impl<'a> Transfer<'a, NeutronTestApp> {
+    /// Open an unordered `transfer` channel over `connection_id` and return its id.
+    pub fn open_channel(&self, connection_id: &str) -> RunnerResult<String> {
+        self.runner
+            .open_channel(TRANSFER_PORT, connection_id, TRANSFER_VERSION, false)
+    }
+
+    /// Answer every transfer packet sent in `events` with `outcome`. Error acks and
+    /// timeouts refund the sender; fees locked by contracts are paid out to `relayer`
+    /// and the sender contract receives the matching sudo callback.
+    pub fn relay(
+        &self,
+        events: &[Event],
+        outcome: &PacketOutcome,
+        relayer: &str,
+    ) -> RunnerResult<Vec<IbcPacket>> {
+        let packets: Vec<IbcPacket> = IbcPacket::from_events(events)?
+            .into_iter()
+            .filter(|p| p.source_port == TRANSFER_PORT)
+            .collect();
+
+        for packet in &packets {
+            self.runner.relay_packet(packet, outcome, relayer)?;
+        }
+
+        Ok(packets)
+    }
+
+    /// Receive `amount` of `base_denom` sent over `path` (e.g. `transfer/channel-0`, or
+    /// `transfer/channel-0/transfer/channel-5` for a multi-hop token) and return the
+    /// `ibc/...` denom minted to `receiver`.
+    ///
+    /// The first hop of `path` must be a channel opened with [`Transfer::open_channel`].
+    /// `memo` is forwarded with the packet, e.g. an ibc-hooks `{"wasm": {...}}` call. An
+    /// error acknowledgement is returned as an error, an asynchronous one as a success.
+    pub fn receive_ibc_tokens(
+        &self,
+        receiver: &str,
+        base_denom: &str,
+        path: &str,
+        amount: u128,
+        memo: Option<String>,
+    ) -> RunnerResult<String> {
+        let hops: Vec<&str> = path.split('/').collect();
+        if hops.len() < 2 || hops.len() % 2 != 0 || hops.iter().any(|h| h.is_empty()) {
+            return Err(RunnerError::GenericError(format!(
+                "invalid path `{}`, expected `port/channel[/port/channel...]`",
+                path
+            )));
+        }
+
+        // the denom as known by the counterparty, without the first hop
+        let denom = hops[2..]
+            .iter()
+            .chain(std::iter::once(&base_denom))
+            .copied()
+            .collect::<Vec<&str>>()
+            .join("/");
+
+        let data = json!({
+            "denom": denom,
+            "amount": amount.to_string(),
+            "sender": "counterparty-sender",
+            "receiver": receiver,
+            "memo": memo.unwrap_or_default(),
+        });
+
+        let packet = IbcPacket {
+            sequence: 0,
+            source_port: String::new(),
+            source_channel: String::new(),
+            destination_port: hops[0].to_string(),
+            destination_channel: hops[1].to_string(),
+            data: data.to_string().into_bytes().into(),
+            timeout_height: Default::default(),
+            timeout_timestamp: self.runner.get_block_time_nanos() as u64 + 3_600_000_000_000,
+        };
+
+        // no acknowledgement is written yet when the application acknowledges asynchronously
+        let ack = self.runner.receive_packet(&packet, receiver)?;
+        if !ack.is_empty() {
+            let ack: Value = serde_json::from_slice(&ack).map_err(|e| {
+                RunnerError::GenericError(format!("invalid acknowledgement: {}", e))
+            })?;
+            if let Some(error) = ack.get("error") {
+                return Err(RunnerError::ExecuteError {
+                    msg: error.as_str().unwrap_or_default().to_string(),
+                });
+            }
+        }
+
+        let hash = self
+            .query_denom_hash(&QueryDenomHashRequest {
+                trace: format!("{}/{}", path, base_denom),
+            })?
+            .hash;
+
+        Ok(format!("ibc/{}", hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, BankMsg, Coin, CosmosMsg};
+    use margined_neutron_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use margined_neutron_std::types::ibc::applications::transfer::v1::{
+        MsgTransfer, QueryDenomTraceRequest,
+    };
+    use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
+    use neutron_sdk::sudo::msg::{RequestPacketTimeoutHeight, SudoMsg};
+    use serde_json::json;
+
+    use crate::sudo_recorder::SudoRecorder;
+    use crate::{Account, Bank, NeutronTestApp, PacketOutcome, Transfer};
+    use test_tube_ntrn::Module;
+
+    fn balance(app: &NeutronTestApp, address: &str) -> u128 {
+        Bank::new(app)
+            .query_balance(&QueryBalanceRequest {
+                address: address.to_string(),
+                denom: "untrn".to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap()
+            .amount
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn transfer_mock_counterparty() {
+        let app = NeutronTestApp::new();
+        let transfer = Transfer::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (sender, relayer) = (&accs[0], &accs[1]);
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+
+        let send = |amount: u128| {
+            transfer
+                .transfer(
+                    MsgTransfer {
+                        source_port: "transfer".to_string(),
+                        source_channel: channel_id.clone(),
+                        token: Some(BaseCoin {
+                            denom: "untrn".to_string(),
+                            amount: amount.to_string(),
+                        }),
+                        sender: sender.address(),
+                        receiver: "cosmos1receiver".to_string(),
+                        timeout_height: None,
+                        timeout_timestamp: app.get_block_time_nanos() as u64 + 3_600_000_000_000,
+                        memo: String::new(),
+                        fee: None,
+                    },
+                    sender,
+                )
+                .unwrap()
+        };
+
+        // error acks and timeouts refund the sender
+        for outcome in [
+            PacketOutcome::Error("failed".to_string()),
+            PacketOutcome::Timeout,
+        ] {
+            let res = send(1_000);
+            let before = balance(&app, &sender.address());
+
+            let packets = transfer
+                .relay(&res.events, &outcome, &relayer.address())
+                .unwrap();
+            assert_eq!(packets.len(), 1);
+            assert_eq!(packets[0].sequence, res.data.sequence);
+            assert_eq!(balance(&app, &sender.address()), before + 1_000);
+        }
+
+        // success acks keep the tokens escrowed
+        let res = send(1_000);
+        let before = balance(&app, &sender.address());
+        transfer
+            .relay(
+                &res.events,
+                &PacketOutcome::transfer_success(),
+                &relayer.address(),
+            )
+            .unwrap();
+        assert_eq!(balance(&app, &sender.address()), before);
+
+        // packets can only be answered once
+        assert!(transfer
+            .relay(
+                &res.events,
+                &PacketOutcome::transfer_success(),
+                &relayer.address()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn transfer_contract_fees_and_callbacks() {
+        let app = NeutronTestApp::new();
+        let transfer = Transfer::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (owner, relayer) = (&accs[0], &accs[1]);
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+
+        let recorder = SudoRecorder::new(&app, &[Coin::new(10_000_000u128, "untrn")], owner);
+        let (ack_fee, timeout_fee) = (1_000u128, 2_000u128);
+        let send = || {
+            recorder
+                .dispatch(
+                    vec![NeutronMsg::IbcTransfer {
+                        source_port: "transfer".to_string(),
+                        source_channel: channel_id.clone(),
+                        token: coin(10_000, "untrn"),
+                        sender: recorder.address.clone(),
+                        receiver: "cosmos1receiver".to_string(),
+                        timeout_height: RequestPacketTimeoutHeight {
+                            revision_number: None,
+                            revision_height: None,
+                        },
+                        timeout_timestamp: app.get_block_time_nanos() as u64 + 3_600_000_000_000,
+                        memo: String::new(),
+                        fee: IbcFee {
+                            recv_fee: vec![],
+                            ack_fee: vec![coin(ack_fee, "untrn")],
+                            timeout_fee: vec![coin(timeout_fee, "untrn")],
+                        },
+                    }
+                    .into()],
+                    owner,
+                )
+                .unwrap()
+        };
+
+        // (outcome, relayer payout, transferred tokens refunded)
+        for (outcome, payout, refunded) in [
+            (PacketOutcome::transfer_success(), ack_fee, false),
+            (PacketOutcome::Error("failed".to_string()), ack_fee, true),
+            (PacketOutcome::Timeout, timeout_fee, true),
+        ] {
+            let contract_before = balance(&app, &recorder.address);
+            let relayer_before = balance(&app, &relayer.address());
+
+            let res = send();
+            assert_eq!(
+                balance(&app, &recorder.address),
+                contract_before - 10_000 - ack_fee - timeout_fee
+            );
+
+            let packets = transfer
+                .relay(&res.events, &outcome, &relayer.address())
+                .unwrap();
+            assert_eq!(packets.len(), 1);
+
+            // the relayer gets the fee of the outcome, the other one goes back to the contract
+            assert_eq!(balance(&app, &relayer.address()), relayer_before + payout);
+            let transferred = if refunded { 0 } else { 10_000 };
+            assert_eq!(
+                balance(&app, &recorder.address),
+                contract_before - transferred - payout
+            );
+
+            let request = match (&outcome, recorder.sudo_calls().last().unwrap()) {
+                (PacketOutcome::Success(_), SudoMsg::Response { request, .. }) => request.clone(),
+                (PacketOutcome::Error(_), SudoMsg::Error { request, details }) => {
+                    assert_eq!(details, "failed");
+                    request.clone()
+                }
+                (PacketOutcome::Timeout, SudoMsg::Timeout { request }) => request.clone(),
+                (outcome, call) => panic!("unexpected sudo call {:?} for {:?}", call, outcome),
+            };
+            assert_eq!(request.sequence, Some(packets[0].sequence));
+            assert_eq!(request.source_channel.as_deref(), Some(channel_id.as_str()));
+        }
+        assert_eq!(recorder.sudo_calls().len(), 3);
+    }
+
+    #[test]
+    fn transfer_receive_ibc_tokens() {
+        let app = NeutronTestApp::new();
+        let transfer = Transfer::new(&app);
+        let bank = Bank::new(&app);
+
+        let receiver = app.init_account(&[]).unwrap();
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+        let path = format!("transfer/{}/transfer/channel-5", channel_id);
+
+        let denom = transfer
+            .receive_ibc_tokens(&receiver.address(), "uatom", &path, 1_000, None)
+            .unwrap();
+        assert!(denom.starts_with("ibc/"));
+
+        let balance = bank
+            .query_balance(&QueryBalanceRequest {
+                address: receiver.address(),
+                denom: denom.clone(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000");
+
+        let trace = transfer
+            .query_denom_trace(&QueryDenomTraceRequest {
+                hash: denom.trim_start_matches("ibc/").to_string(),
+            })
+            .unwrap()
+            .denom_trace
+            .unwrap();
+        assert_eq!(trace.path, path);
+        assert_eq!(trace.base_denom, "uatom");
+
+        // ibc-hooks calls to a missing contract are answered with an error ack
+        let memo = format!(
+            r#"{{"wasm":{{"contract":"{}","msg":{{}}}}}}"#,
+            receiver.address()
+        );
+        assert!(transfer
+            .receive_ibc_tokens(&receiver.address(), "uatom", &path, 1_000, Some(memo))
+            .is_err());
+
+        // the first hop must be an open channel
+        assert!(transfer
+            .receive_ibc_tokens(
+                &receiver.address(),
+                "uatom",
+                "transfer/channel-99",
+                1_000,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn transfer_receive_ibc_tokens_with_hook() {
+        let app = NeutronTestApp::new();
+        let transfer = Transfer::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let (owner, recipient) = (&accs[0], &accs[1]);
+
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+        let path = format!("transfer/{}", channel_id);
+
+        let recorder = SudoRecorder::new(&app, &[], owner);
+        let denom = transfer
+            .receive_ibc_tokens(&recipient.address(), "uatom", &path, 1_000, None)
+            .unwrap();
+
+        // the hook receives the tokens and forwards part of them
+        let memo = json!({
+            "wasm": {
+                "contract": recorder.address,
+                "msg": {
+                    "dispatch": {
+                        "msgs": [CosmosMsg::<NeutronMsg>::Bank(BankMsg::Send {
+                            to_address: recipient.address(),
+                            amount: vec![coin(400, &denom)],
+                        })],
+                    },
+                },
+            },
+        });
+        let hooked_denom = transfer
+            .receive_ibc_tokens(
+                &recorder.address,
+                "uatom",
+                &path,
+                1_000,
+                Some(memo.to_string()),
+            )
+            .unwrap();
+        assert_eq!(hooked_denom, denom);
+
+        let balance = |address: &str| {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: address.to_string(),
+                    denom: denom.clone(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+        };
+        assert_eq!(balance(&recorder.address), "600");
+        assert_eq!(balance(&recipient.address()), "1400");
+    }
+}
@@ -0,0 +1,288 @@
+use cosmwasm_std::Event;
+use margined_neutron_std::shim::Any;
+use margined_neutron_std::types::cosmos::upgrade::v1beta1::{
+    MsgCancelUpgrade, MsgCancelUpgradeResponse, MsgSoftwareUpgrade, MsgSoftwareUpgradeResponse,
+    Plan, QueryAppliedPlanRequest, QueryAppliedPlanResponse, QueryAuthorityRequest,
+    QueryAuthorityResponse, QueryCurrentPlanRequest, QueryCurrentPlanResponse,
+    QueryModuleVersionsRequest, QueryModuleVersionsResponse,
+};
+use prost::Message;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+use test_tube_ntrn::{fn_execute, fn_query};
+
+use test_tube_ntrn::module::Module;
+use test_tube_ntrn::runner::Runner;
+
+use crate::{Admin, NeutronTestApp, BLOCK_TIME_SECONDS};
+
+pub struct Upgrade<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Upgrade<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Upgrade<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub software_upgrade: MsgSoftwareUpgrade["/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade"] => MsgSoftwareUpgradeResponse
+    }
+
+    fn_execute! {
+        pub cancel_upgrade: MsgCancelUpgrade["/cosmos.upgrade.v1beta1.MsgCancelUpgrade"] => MsgCancelUpgradeResponse
+    }
+
+    fn_query! {
+        pub query_current_plan ["/cosmos.upgrade.v1beta1.Query/CurrentPlan"]: QueryCurrentPlanRequest => QueryCurrentPlanResponse
+    }
+
+    fn_query! {
+        pub query_applied_plan ["/cosmos.upgrade.v1beta1.Query/AppliedPlan"]: QueryAppliedPlanRequest => QueryAppliedPlanResponse
+    }
+
+    fn_query! {
+        pub query_module_versions ["/cosmos.upgrade.v1beta1.Query/ModuleVersions"]: QueryModuleVersionsRequest => QueryModuleVersionsResponse
+    }
+
+    fn_query! {
+        pub query_authority ["/cosmos.upgrade.v1beta1.Query/Authority"]: QueryAuthorityRequest => QueryAuthorityResponse
+    }
+
+    /// `MsgSoftwareUpgrade` scheduling `name` at `height`, to be executed by `authority`
+    /// in a proposal.
+    pub fn software_upgrade_msg(authority: &str, name: &str, height: i64, info: &str) -> Any {
+        Any {
+            type_url: "/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade".to_string(),
+            value: MsgSoftwareUpgrade {
+                authority: authority.to_string(),
+                plan: Some(Plan {
+                    name: name.to_string(),
+                    height,
+                    info: info.to_string(),
+                    ..Default::default()
+                }),
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    /// The upgrade plan scheduled, if any.
+    pub fn current_plan(&self) -> RunnerResult<Option<Plan>> {
+        Ok(self.query_current_plan(&QueryCurrentPlanRequest {})?.plan)
+    }
+
+    /// Height at which the plan `name` was applied, `None` if it was not.
+    pub fn applied_height(&self, name: &str) -> RunnerResult<Option<i64>> {
+        let height = self
+            .query_applied_plan(&QueryAppliedPlanRequest {
+                name: name.to_string(),
+            })?
+            .height;
+
+        Ok(Some(height).filter(|h| *h > 0))
+    }
+}
+
+/// Upgrade rehearsal: the plan is scheduled by the admin module, which is the upgrade
+/// authority on Neutron. There is no gov path, a `MsgSoftwareUpgrade` passed by a gov
+/// proposal is rejected when executed.
+impl<'a> Upgrade<'a, NeutronTestApp> {
+    /// Schedule the upgrade `name` at `height`.
+    pub fn schedule_upgrade(&self, name: &str, height: i64, info: &str) -> RunnerResult<()> {
+        let admin = Admin::new(self.runner);
+        let authority = admin.authority()?;
+
+        admin.execute_as_admin(vec![Self::software_upgrade_msg(
+            &authority, name, height, info,
+        )])?;
+
+        Ok(())
+    }
+
+    /// Cancel the scheduled upgrade.
+    pub fn cancel_scheduled_upgrade(&self) -> RunnerResult<()> {
+        let admin = Admin::new(self.runner);
+
+        let msg = MsgCancelUpgrade {
+            authority: admin.authority()?,
+        };
+
+        admin.execute_as_admin(vec![Any {
+            type_url: "/cosmos.upgrade.v1beta1.MsgCancelUpgrade".to_string(),
+            value: msg.encode_to_vec(),
+        }])?;
+
+        Ok(())
+    }
+
+    /// Produce blocks until the chain halts at the scheduled plan, for at most
+    /// `max_blocks` blocks. The chain stays at the last height before the plan until
+    /// [`Upgrade::apply_upgrade`] is called.
+    ///
+    /// While halted, txs and new accounts fail with the halt error and
+    /// `increase_time` panics, as none of their blocks can be finalized.
+    pub fn advance_to_upgrade(&self, max_blocks: u64) -> RunnerResult<UpgradeHalt> {
+        let plan = self
+            .current_plan()?
+            .ok_or_else(|| RunnerError::GenericError("no upgrade scheduled".to_string()))?;
+        let halt_msg = format!("UPGRADE \"{}\" NEEDED", plan.name);
+
+        for _ in 0..max_blocks {
+            match self.runner.advance_block(BLOCK_TIME_SECONDS) {
+                Ok(_) => {}
+                Err(RunnerError::ExecuteError { msg }) if msg.contains(&halt_msg) => {
+                    return Ok(UpgradeHalt {
+                        name: plan.name,
+                        height: self.runner.get_block_height() + 1,
+                        info: plan.info,
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(RunnerError::GenericError(format!(
+            "upgrade `{}` not reached within {} blocks",
+            plan.name, max_blocks
+        )))
+    }
+
+    /// Resume a chain halted at the plan `name`: register a handler unless the app has
+    /// one for this plan, and produce the upgrade block. Returns the events of that block.
+    pub fn apply_upgrade(&self, name: &str) -> RunnerResult<Vec<Event>> {
+        self.runner.set_upgrade_handler(name);
+
+        let events = self.runner.advance_block(BLOCK_TIME_SECONDS)?;
+
+        if self.applied_height(name)?.is_none() {
+            return Err(RunnerError::GenericError(format!(
+                "upgrade `{}` was not applied",
+                name
+            )));
+        }
+
+        Ok(events)
+    }
+}
+
+/// Chain halted at an upgrade plan, see [`Upgrade::advance_to_upgrade`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeHalt {
+    pub name: String,
+    pub height: i64,
+    pub info: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::types::cosmos::bank::v1beta1::MsgSend;
+    use margined_neutron_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+
+    use margined_neutron_std::types::cosmos::gov::v1::{ProposalStatus, VoteOption};
+
+    use crate::{Account, Bank, GovWithAppAccess, NeutronTestApp, ProposalOptions, Upgrade};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn upgrade_halt_and_resume() {
+        let app = NeutronTestApp::new();
+        let upgrade = Upgrade::new(&app);
+        let acc = app
+            .init_account(&[Coin::new(1_000_000_000u128, "untrn")])
+            .unwrap();
+
+        assert_eq!(upgrade.current_plan().unwrap(), None);
+
+        let height = app.get_block_height() + 5;
+        upgrade
+            .schedule_upgrade("v-test", height, "rehearsal")
+            .unwrap();
+        assert_eq!(upgrade.current_plan().unwrap().unwrap().height, height);
+
+        let halt = upgrade.advance_to_upgrade(20).unwrap();
+        assert_eq!(halt.name, "v-test");
+        assert_eq!(halt.height, height);
+        assert_eq!(halt.info, "rehearsal");
+        assert_eq!(app.get_block_height(), height - 1);
+
+        // the chain stays halted until a handler is registered
+        assert!(app.advance_block(3).is_err());
+        assert_eq!(app.get_block_height(), height - 1);
+
+        // so do txs, which return the halt error instead of aborting
+        let err = Bank::new(&app)
+            .send(
+                MsgSend {
+                    from_address: acc.address(),
+                    to_address: acc.address(),
+                    amount: vec![BaseCoin {
+                        denom: "untrn".to_string(),
+                        amount: "1".to_string(),
+                    }],
+                },
+                &acc,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("UPGRADE \"v-test\" NEEDED"));
+        assert_eq!(app.get_block_height(), height - 1);
+
+        upgrade.apply_upgrade("v-test").unwrap();
+        assert_eq!(app.get_block_height(), height);
+        assert_eq!(upgrade.applied_height("v-test").unwrap(), Some(height));
+        assert_eq!(upgrade.current_plan().unwrap(), None);
+
+        app.advance_block(3).unwrap();
+
+        // cancelled plans are never reached
+        upgrade
+            .schedule_upgrade("v-cancelled", app.get_block_height() + 5, "")
+            .unwrap();
+        upgrade.cancel_scheduled_upgrade().unwrap();
+        assert!(upgrade.advance_to_upgrade(10).is_err());
+    }
+
+    #[test]
+    fn upgrade_cannot_be_scheduled_by_gov() {
+        let app = NeutronTestApp::new();
+        let upgrade = Upgrade::new(&app);
+        let proposer = app
+            .init_account(&[Coin::new(100_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let gov_authority = app.get_module_address("gov").unwrap();
+        let outcome = GovWithAppAccess::new(&app)
+            .run_proposal(
+                vec![Upgrade::<NeutronTestApp>::software_upgrade_msg(
+                    &gov_authority,
+                    "v-gov",
+                    app.get_block_height() + 100,
+                    "",
+                )],
+                VoteOption::Yes,
+                ProposalOptions {
+                    title: "upgrade".to_string(),
+                    summary: "upgrade".to_string(),
+                    ..Default::default()
+                },
+                &proposer,
+            )
+            .unwrap();
+
+        // the admin module is the upgrade authority
+        assert_eq!(outcome.status, ProposalStatus::Failed);
+        assert!(outcome.failed_reason.contains(
+            &upgrade
+                .query_authority(&Default::default())
+                .unwrap()
+                .address
+        ));
+        assert_eq!(upgrade.current_plan().unwrap(), None);
+    }
+}
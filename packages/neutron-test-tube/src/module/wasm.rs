@@ -17,6 +17,8 @@ use test_tube_ntrn::{
     runner::Runner,
 };
 
+use crate::NeutronTestApp;
+
 pub struct Wasm<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -150,3 +152,114 @@ where
             .map_err(RunnerError::DecodeError)
     }
 }
+
+impl<'a> Wasm<'a, NeutronTestApp> {
+    /// Smart query against the contract state committed at `height`, e.g. to compare
+    /// the state before and after a sequence of blocks.
+    pub fn query_at_height<M, Res>(&self, height: u64, contract: &str, msg: &M) -> RunnerResult<Res>
+    where
+        M: ?Sized + Serialize,
+        Res: ?Sized + DeserializeOwned,
+    {
+        let res = self
+            .runner
+            .query_at_height::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>(
+                height,
+                "/cosmwasm.wasm.v1.Query/SmartContractState",
+                &QuerySmartContractStateRequest {
+                    address: contract.to_owned(),
+                    query_data: serde_json::to_vec(msg).map_err(EncodeError::JsonEncodeError)?,
+                },
+            )?;
+
+        serde_json::from_slice(&res.data)
+            .map_err(DecodeError::JsonDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Coin, Empty};
+    use cw1_whitelist::msg::{AdminListResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+    use crate::{Account, NeutronTestApp, Wasm};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn wasm_query_at_height() {
+        let app = NeutronTestApp::new();
+        let wasm = Wasm::new(&app);
+
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000u128, "untrn")], 2)
+            .unwrap();
+        let admin = &accs[0];
+        let new_admin = &accs[1];
+
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, admin)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![admin.address()],
+                    mutable: true,
+                },
+                None,
+                Some("query at height"),
+                &[],
+                admin,
+            )
+            .unwrap()
+            .data
+            .address;
+        let instantiated_at = app.get_block_height() as u64;
+
+        wasm.execute(
+            &contract_addr,
+            &ExecuteMsg::<Empty>::UpdateAdmins {
+                admins: vec![new_admin.address()],
+            },
+            &[],
+            admin,
+        )
+        .unwrap();
+        app.increase_time(10);
+
+        let latest: AdminListResponse = wasm
+            .query(&contract_addr, &QueryMsg::<Empty>::AdminList {})
+            .unwrap();
+        assert_eq!(latest.admins, vec![new_admin.address()]);
+
+        // the admins before the update are still readable
+        let before: AdminListResponse = wasm
+            .query_at_height(
+                instantiated_at,
+                &contract_addr,
+                &QueryMsg::<Empty>::AdminList {},
+            )
+            .unwrap();
+        assert_eq!(before.admins, vec![admin.address()]);
+
+        // the contract did not exist before its instantiation
+        wasm.query_at_height::<_, AdminListResponse>(
+            instantiated_at - 1,
+            &contract_addr,
+            &QueryMsg::<Empty>::AdminList {},
+        )
+        .unwrap_err();
+
+        // heights that were not committed yet cannot be queried
+        wasm.query_at_height::<_, AdminListResponse>(
+            app.get_block_height() as u64 + 1,
+            &contract_addr,
+            &QueryMsg::<Empty>::AdminList {},
+        )
+        .unwrap_err();
+    }
+}
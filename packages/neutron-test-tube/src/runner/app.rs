@@ -1,17 +1,26 @@
 use cosmrs::Any;
-use cosmwasm_std::{Coin, Timestamp};
+use cosmwasm_std::{Coin, DecCoin, Event, Timestamp};
 use prost::Message;
 use test_tube_ntrn::account::SigningAccount;
+use test_tube_ntrn::module::Module;
 
 use test_tube_ntrn::runner::result::{RunnerExecuteResult, RunnerResult};
 use test_tube_ntrn::runner::Runner;
 use test_tube_ntrn::{runner::app::SlinkyPrices, BaseApp};
 
+use crate::runner::ibc::{IbcPacket, PacketOutcome};
+use crate::runner::params::ModuleParams;
+use crate::Admin;
+
 const FEE_DENOM: &str = "untrn";
 const NEUTRON_ADDRESS_PREFIX: &str = "neutron";
 const CHAIN_ID: &str = "neutron-666";
 const DEFAULT_GAS_ADJUSTMENT: f64 = 1.2;
 
+/// Seconds between two blocks, the time `FinalizeBlock` advances the chain for every
+/// executed transaction.
+pub const BLOCK_TIME_SECONDS: u64 = 3;
+
 #[derive(Debug, PartialEq)]
 pub struct NeutronTestApp {
     inner: BaseApp,
@@ -75,11 +84,107 @@ impl NeutronTestApp {
             .get_first_validator_signing_account(denom, gas_adjustment)
     }
 
+    /// Get the bech32 address of a module account
+    pub fn get_module_address(&self, module_name: &str) -> RunnerResult<String> {
+        self.inner.get_module_address(module_name)
+    }
+
+    /// Decode the JSON encoded message of an admin `ProposalExecuteMessage` to protobuf.
+    pub fn admin_proposal_message(&self, msg_json: &str) -> RunnerResult<Any> {
+        self.inner.admin_proposal_message(msg_json)
+    }
+
+    /// Execute the messages of an admin module `MsgSubmitProposal` and return the
+    /// response of each message, in order.
+    pub fn execute_admin_proposal<M: Message>(&self, proposal: &M) -> RunnerResult<Vec<Any>> {
+        self.inner.execute_admin_proposal(proposal)
+    }
+
+    /// Store the result of a registered KV interchain query without verifying proofs.
+    pub fn submit_kv_query_result<M: Message>(
+        &self,
+        query_id: u64,
+        query_result: &M,
+    ) -> RunnerResult<()> {
+        self.inner.submit_kv_query_result(query_id, query_result)
+    }
+
+    /// Deliver a remote transaction to the owner of a registered TX interchain query.
+    pub fn submit_tx_query_result(
+        &self,
+        query_id: u64,
+        revision_number: u64,
+        revision_height: u64,
+        data: &[u8],
+    ) -> RunnerResult<()> {
+        self.inner
+            .submit_tx_query_result(query_id, revision_number, revision_height, data)
+    }
+
+    /// Register an upgrade handler for the plan `name` if the app has none.
+    pub fn set_upgrade_handler(&self, name: &str) {
+        self.inner.set_upgrade_handler(name)
+    }
+
+    /// Set the bank metadata of any denom.
+    pub fn set_denom_metadata<M: Message>(&self, metadata: &M) -> RunnerResult<()> {
+        self.inner.set_denom_metadata(metadata)
+    }
+
+    /// Create a client and an open connection to a mock counterparty chain.
+    /// Proofs are never checked, so channels and packets are driven from the tests.
+    pub fn create_mock_connection(&self, counterparty_chain_id: &str) -> RunnerResult<String> {
+        self.inner.create_mock_connection(counterparty_chain_id)
+    }
+
+    /// Complete the handshake of a channel opened by this chain on a mock connection.
+    pub fn open_ack_channel(&self, port_id: &str, channel_id: &str) -> RunnerResult<()> {
+        self.inner.open_ack_channel(port_id, channel_id)
+    }
+
+    /// Open a channel on `port_id` over a mock connection and return its id.
+    pub fn open_channel(
+        &self,
+        port_id: &str,
+        connection_id: &str,
+        version: &str,
+        ordered: bool,
+    ) -> RunnerResult<String> {
+        self.inner
+            .open_channel(port_id, connection_id, version, ordered)
+    }
+
+    /// Answer a packet sent by this chain on a mock connection, on behalf of `relayer`.
+    pub fn relay_packet(
+        &self,
+        packet: &IbcPacket,
+        outcome: &PacketOutcome,
+        relayer: &str,
+    ) -> RunnerResult<()> {
+        match outcome.acknowledgement() {
+            Some(ack) => self.inner.acknowledge_packet(packet, &ack, relayer),
+            None => self.inner.timeout_packet(packet, relayer),
+        }
+    }
+
+    /// Deliver a packet from the mock counterparty and return the acknowledgement written
+    /// by the application (empty if asynchronous). The sequence and source are taken from
+    /// the destination channel.
+    pub fn receive_packet(&self, packet: &IbcPacket, relayer: &str) -> RunnerResult<Vec<u8>> {
+        self.inner.receive_packet(packet, relayer)
+    }
+
     /// Increase the time of the blockchain by the given number of seconds.
     pub fn increase_time(&self, seconds: u64) {
         self.inner.increase_time(seconds)
     }
 
+    /// Finalize an empty block `seconds` after the current one and return the
+    /// begin/end blocker events.
+    pub fn advance_block(&self, seconds: u64) -> RunnerResult<Vec<Event>> {
+        self.inner.advance_block(seconds)
+    }
+
     /// Set the slinky prices
     pub fn set_slinky_prices(&self, prices: &[SlinkyPrices]) {
         self.inner.set_slinky_prices(prices)
@@ -108,6 +213,25 @@ impl NeutronTestApp {
         self.inner.simulate_tx(msgs, signer)
     }
 
+    /// Current fee market gas price for `denom`.
+    pub fn query_gas_price(&self, denom: &str) -> RunnerResult<DecCoin> {
+        self.inner.query_gas_price(denom)
+    }
+
+    /// Current fee market gas price of every denom accepted for fees.
+    pub fn query_gas_prices(&self) -> RunnerResult<Vec<DecCoin>> {
+        self.inner.query_gas_prices()
+    }
+
+    /// Same as `query`, but against the state committed at `height` instead of the latest one.
+    pub fn query_at_height<Q, R>(&self, height: u64, path: &str, q: &Q) -> RunnerResult<R>
+    where
+        Q: ::prost::Message,
+        R: ::prost::Message + Default,
+    {
+        self.inner.query_at_height(height, path, q)
+    }
+
     /// Set parameter set for a given subspace.
     pub fn set_param_set(&self, subspace: &str, pset: impl Into<Any>) -> RunnerResult<()> {
         self.inner.set_param_set(subspace, pset)
@@ -121,6 +245,26 @@ impl NeutronTestApp {
     ) -> RunnerResult<P> {
         self.inner.get_param_set(subspace, type_url)
     }
+
+    /// Update the params of a module through its `MsgUpdateParams`, signed by the admin module.
+    pub fn update_params<P: ModuleParams>(&self, params: P) -> RunnerResult<()> {
+        let admin = Admin::new(self);
+        let authority = admin.authority()?;
+
+        admin.execute_as_admin(vec![params.update_msg(authority)])?;
+
+        Ok(())
+    }
+
+    /// Get the current params of a module.
+    pub fn get_params<P: ModuleParams>(&self) -> RunnerResult<P> {
+        let res = self.query::<P::QueryRequest, P::QueryResponse>(
+            P::QUERY_PATH,
+            &P::QueryRequest::default(),
+        )?;
+
+        P::from_response(res)
+    }
 }
 
 impl<'a> Runner<'a> for NeutronTestApp {
@@ -158,15 +302,16 @@ impl<'a> Runner<'a> for NeutronTestApp {
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{coins, Coin};
+    use cosmwasm_std::{coins, Coin, Decimal, Uint128};
+    use margined_neutron_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
     use margined_neutron_std::types::osmosis::tokenfactory::v1beta1::{
         MsgCreateDenom, MsgCreateDenomResponse, QueryParamsRequest, QueryParamsResponse,
     };
 
-    use crate::module::Wasm;
-    use crate::runner::app::NeutronTestApp;
+    use crate::module::{Bank, Wasm};
+    use crate::runner::app::{NeutronTestApp, DEFAULT_GAS_ADJUSTMENT};
 
-    use test_tube_ntrn::account::Account;
+    use test_tube_ntrn::account::{Account, FeeSetting};
     use test_tube_ntrn::module::Module;
     use test_tube_ntrn::runner::*;
     use test_tube_ntrn::ExecuteResponse;
@@ -374,4 +519,82 @@ mod tests {
         assert_eq!(admin_list.admins, new_admins);
         assert!(admin_list.mutable);
     }
+
+    #[test]
+    fn test_market_fee_setting() {
+        let app = NeutronTestApp::default();
+
+        let gas_price = app.query_gas_price("untrn").unwrap();
+        assert_eq!(gas_price.denom, "untrn");
+        assert!(!gas_price.amount.is_zero());
+        assert!(app
+            .query_gas_prices()
+            .unwrap()
+            .iter()
+            .any(|price| price.denom == "untrn"));
+
+        let multiplier = Decimal::percent(150);
+        let acc = app.init_account(&coins(100_000_000_000, "untrn")).unwrap();
+
+        // new accounts pay the fee market price by default
+        assert_eq!(
+            acc.fee_setting(),
+            &FeeSetting::Market {
+                denom: "untrn".to_string(),
+                gas_adjustment: DEFAULT_GAS_ADJUSTMENT,
+                price_multiplier: Decimal::one(),
+            }
+        );
+
+        let acc = acc.with_fee_setting(FeeSetting::Market {
+            denom: "untrn".to_string(),
+            gas_adjustment: DEFAULT_GAS_ADJUSTMENT,
+            price_multiplier: multiplier,
+        });
+
+        let sender = acc.address();
+        let create_denom = |subdenom: &str| MsgCreateDenom {
+            sender: sender.clone(),
+            subdenom: subdenom.to_string(),
+        };
+
+        let gas_price = app.query_gas_price("untrn").unwrap();
+        let res: ExecuteResponse<MsgCreateDenomResponse> = app
+            .execute(
+                create_denom("marketfee"),
+                "/osmosis.tokenfactory.v1beta1.MsgCreateDenom",
+                &acc,
+            )
+            .unwrap();
+
+        // the fee is the gas limit priced at the fee market gas price times the multiplier
+        let paid = 100_000_000_000u128
+            - Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: sender.clone(),
+                    denom: "untrn".to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+                .parse::<u128>()
+                .unwrap();
+        let fee = Uint128::from(res.gas_info.gas_wanted).mul_ceil(gas_price.amount * multiplier);
+        assert_eq!(paid, fee.u128());
+
+        // denoms not accepted by the fee market are refused
+        let acc = acc.with_fee_setting(FeeSetting::Market {
+            denom: "uunknown".to_string(),
+            gas_adjustment: DEFAULT_GAS_ADJUSTMENT,
+            price_multiplier: Decimal::one(),
+        });
+        assert!(app
+            .execute::<_, MsgCreateDenomResponse>(
+                create_denom("unknownfee"),
+                "/osmosis.tokenfactory.v1beta1.MsgCreateDenom",
+                &acc,
+            )
+            .is_err());
+    }
 }
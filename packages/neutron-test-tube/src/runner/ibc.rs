@@ -0,0 +1,120 @@
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxMsgData;
+use cosmwasm_std::{Binary, Event};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+
+/// Height on the counterparty chain, as found in `timeout_height`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IbcHeight {
+    pub revision_number: u64,
+    pub revision_height: u64,
+}
+
+/// Packet sent by the app to the mock counterparty, parsed from `send_packet` events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IbcPacket {
+    pub sequence: u64,
+    pub source_port: String,
+    pub source_channel: String,
+    pub destination_port: String,
+    pub destination_channel: String,
+    pub data: Binary,
+    pub timeout_height: IbcHeight,
+    pub timeout_timestamp: u64,
+}
+
+impl IbcPacket {
+    /// Collect the packets of every `send_packet` event in `events`.
+    pub fn from_events(events: &[Event]) -> RunnerResult<Vec<IbcPacket>> {
+        events
+            .iter()
+            .filter(|e| e.ty == "send_packet")
+            .map(Self::from_event)
+            .collect()
+    }
+
+    fn from_event(event: &Event) -> RunnerResult<IbcPacket> {
+        let attr = |key: &str| -> RunnerResult<&str> {
+            event
+                .attributes
+                .iter()
+                .find(|a| a.key == key)
+                .map(|a| a.value.as_str())
+                .ok_or_else(|| {
+                    RunnerError::GenericError(format!("send_packet event without `{}`", key))
+                })
+        };
+        let parse_err = |key: &str| {
+            RunnerError::GenericError(format!("invalid `{}` in send_packet event", key))
+        };
+
+        let (revision_number, revision_height) = attr("packet_timeout_height")?
+            .split_once('-')
+            .ok_or_else(|| parse_err("packet_timeout_height"))?;
+
+        Ok(IbcPacket {
+            sequence: attr("packet_sequence")?
+                .parse()
+                .map_err(|_| parse_err("packet_sequence"))?,
+            source_port: attr("packet_src_port")?.to_string(),
+            source_channel: attr("packet_src_channel")?.to_string(),
+            destination_port: attr("packet_dst_port")?.to_string(),
+            destination_channel: attr("packet_dst_channel")?.to_string(),
+            data: hex::decode(attr("packet_data_hex")?)
+                .map_err(|_| parse_err("packet_data_hex"))?
+                .into(),
+            timeout_height: IbcHeight {
+                revision_number: revision_number
+                    .parse()
+                    .map_err(|_| parse_err("packet_timeout_height"))?,
+                revision_height: revision_height
+                    .parse()
+                    .map_err(|_| parse_err("packet_timeout_height"))?,
+            },
+            timeout_timestamp: attr("packet_timeout_timestamp")?
+                .parse()
+                .map_err(|_| parse_err("packet_timeout_timestamp"))?,
+        })
+    }
+}
+
+/// How the mock counterparty answers a packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketOutcome {
+    /// Success acknowledgement carrying the given result bytes.
+    Success(Binary),
+    /// Error acknowledgement.
+    Error(String),
+    /// The packet is never received and times out.
+    Timeout,
+}
+
+impl PacketOutcome {
+    /// Success acknowledgement of an interchain account tx, with one response per message.
+    pub fn ica_success(msg_responses: Vec<cosmrs::Any>) -> Self {
+        let data = TxMsgData {
+            msg_responses,
+            ..Default::default()
+        };
+
+        PacketOutcome::Success(data.encode_to_vec().into())
+    }
+
+    /// Success acknowledgement of an ICS-20 transfer.
+    pub fn transfer_success() -> Self {
+        PacketOutcome::Success(Binary::from(vec![1u8]))
+    }
+
+    /// JSON encoded `ibc.core.channel.v1.Acknowledgement`, `None` for timeouts.
+    pub fn acknowledgement(&self) -> Option<Vec<u8>> {
+        match self {
+            PacketOutcome::Success(result) => Some(json!({ "result": result }).to_string()),
+            PacketOutcome::Error(error) => Some(json!({ "error": error }).to_string()),
+            PacketOutcome::Timeout => None,
+        }
+        .map(String::into_bytes)
+    }
+}
@@ -1,4 +1,8 @@
 pub mod app;
+pub mod ibc;
+pub mod neutron_msg;
+pub mod params;
+pub mod replay;
 
 #[cfg(test)]
 mod tests {
@@ -155,4 +159,260 @@ mod tests {
             format!("factory/{}/{}", signer.address(), denom)
         );
     }
+
+    #[test]
+    fn test_execute_cosmos_msgs_staking_and_distribution() {
+        use cosmwasm_std::{DistributionMsg, StakingMsg};
+        use margined_neutron_std::types::cosmos::distribution::v1beta1::{
+            MsgSetWithdrawAddressResponse, QueryDelegatorWithdrawAddressRequest,
+        };
+        use margined_neutron_std::types::cosmos::staking::v1beta1::{
+            MsgDelegateResponse, QueryDelegationRequest,
+        };
+        use margined_neutron_std::types::cosmwasm::wasm::v1::{
+            MsgInstantiateContract2Response, QueryContractInfoRequest, QueryContractInfoResponse,
+        };
+
+        use crate::{Distribution, Staking};
+
+        let app = NeutronTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+        let staking = Staking::new(&app);
+
+        let validator = staking
+            .bonded_validators()
+            .unwrap()
+            .remove(0)
+            .operator_address;
+
+        // StakingMsg::Delegate
+        app.execute_cosmos_msgs::<MsgDelegateResponse>(
+            &[CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: validator.clone(),
+                amount: Coin::new(1_000_000u128, "untrn"),
+            })],
+            &signer,
+        )
+        .unwrap();
+        let delegation = staking
+            .query_delegation(&QueryDelegationRequest {
+                delegator_addr: signer.address(),
+                validator_addr: validator,
+            })
+            .unwrap()
+            .delegation_response
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(delegation.amount, "1000000");
+
+        // DistributionMsg::SetWithdrawAddress
+        let withdraw = app.init_account(&[]).unwrap();
+        app.execute_cosmos_msgs::<MsgSetWithdrawAddressResponse>(
+            &[CosmosMsg::Distribution(
+                DistributionMsg::SetWithdrawAddress {
+                    address: withdraw.address(),
+                },
+            )],
+            &signer,
+        )
+        .unwrap();
+        let withdraw_address = Distribution::new(&app)
+            .query_delegator_withdraw_address(&QueryDelegatorWithdrawAddressRequest {
+                delegator_address: signer.address(),
+            })
+            .unwrap()
+            .withdraw_address;
+        assert_eq!(withdraw_address, withdraw.address());
+
+        // WasmMsg::Instantiate2
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = Wasm::new(&app)
+            .store_code(&wasm_byte_code, None, &signer)
+            .unwrap()
+            .data
+            .code_id;
+        let instantiate2 = CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+            admin: Some(signer.address()),
+            code_id,
+            label: "whitelist".to_string(),
+            msg: to_json_binary(&InstantiateMsg {
+                admins: vec![signer.address()],
+                mutable: false,
+            })
+            .unwrap(),
+            funds: vec![],
+            salt: b"salt".to_vec().into(),
+        });
+        let res = app
+            .execute_cosmos_msgs::<MsgInstantiateContract2Response>(
+                &[instantiate2.clone()],
+                &signer,
+            )
+            .unwrap();
+        let contract_info = app
+            .query::<QueryContractInfoRequest, QueryContractInfoResponse>(
+                "/cosmwasm.wasm.v1.Query/ContractInfo",
+                &QueryContractInfoRequest {
+                    address: res.data.address.clone(),
+                },
+            )
+            .unwrap()
+            .contract_info
+            .unwrap();
+        assert_eq!(contract_info.code_id, code_id);
+        assert_eq!(contract_info.creator, signer.address());
+        assert_eq!(contract_info.admin, signer.address());
+        assert_eq!(contract_info.label, "whitelist");
+
+        // the salt is part of the address, so it cannot be reused
+        assert!(app
+            .execute_cosmos_msgs::<MsgInstantiateContract2Response>(&[instantiate2], &signer)
+            .is_err());
+
+        // BankMsg::Burn has no SDK message
+        let err = app
+            .execute_cosmos_msgs::<MsgSendResponse>(
+                &[CosmosMsg::Bank(BankMsg::Burn {
+                    amount: vec![Coin::new(1u128, "untrn")],
+                })],
+                &signer,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("BankMsg::Burn"));
+    }
+
+    #[test]
+    fn test_execute_cosmos_msgs_gov_and_ibc() {
+        use cosmwasm_std::{
+            Decimal, GovMsg, IbcMsg, IbcTimeout, StakingMsg, Timestamp, VoteOption,
+            WeightedVoteOption,
+        };
+        use margined_neutron_std::types::cosmos::gov::v1::{
+            MsgSubmitProposal, MsgVoteResponse, MsgVoteWeightedResponse, QueryTallyResultRequest,
+        };
+        use margined_neutron_std::types::cosmos::staking::v1beta1::MsgDelegateResponse;
+        use margined_neutron_std::types::ibc::applications::transfer::v1::MsgTransferResponse;
+        use serde_json::Value;
+
+        use crate::{Gov, GovWithAppAccess, PacketOutcome, Staking, Transfer};
+
+        let app = NeutronTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(1_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        // votes weigh the stake of the voter
+        let validator = Staking::new(&app)
+            .bonded_validators()
+            .unwrap()
+            .remove(0)
+            .operator_address;
+        app.execute_cosmos_msgs::<MsgDelegateResponse>(
+            &[CosmosMsg::Staking(StakingMsg::Delegate {
+                validator,
+                amount: Coin::new(1_000_000u128, "untrn"),
+            })],
+            &signer,
+        )
+        .unwrap();
+
+        let gov = Gov::new(&app);
+        let proposal_id = gov
+            .submit_proposal_v1(
+                MsgSubmitProposal {
+                    messages: vec![],
+                    initial_deposit: GovWithAppAccess::new(&app).params().unwrap().min_deposit,
+                    proposer: signer.address(),
+                    metadata: String::new(),
+                    title: "vote".to_string(),
+                    summary: "vote".to_string(),
+                    expedited: false,
+                },
+                &signer,
+            )
+            .unwrap()
+            .data
+            .proposal_id;
+        let tally = || {
+            gov.query_tally_result(&QueryTallyResultRequest { proposal_id })
+                .unwrap()
+                .tally
+                .unwrap()
+        };
+
+        // GovMsg::Vote
+        app.execute_cosmos_msgs::<MsgVoteResponse>(
+            &[CosmosMsg::Gov(GovMsg::Vote {
+                proposal_id,
+                option: VoteOption::Yes,
+            })],
+            &signer,
+        )
+        .unwrap();
+        let res = tally();
+        assert_eq!(res.yes_count, "1000000");
+        assert_eq!(res.no_count, "0");
+
+        // GovMsg::VoteWeighted replaces the previous vote
+        app.execute_cosmos_msgs::<MsgVoteWeightedResponse>(
+            &[CosmosMsg::Gov(GovMsg::VoteWeighted {
+                proposal_id,
+                options: vec![
+                    WeightedVoteOption {
+                        option: VoteOption::Yes,
+                        weight: Decimal::percent(60),
+                    },
+                    WeightedVoteOption {
+                        option: VoteOption::No,
+                        weight: Decimal::percent(40),
+                    },
+                ],
+            })],
+            &signer,
+        )
+        .unwrap();
+        let res = tally();
+        assert_eq!(res.yes_count, "600000");
+        assert_eq!(res.no_count, "400000");
+
+        // IbcMsg::Transfer
+        let transfer = Transfer::new(&app);
+        let connection_id = app.create_mock_connection("cosmoshub-4").unwrap();
+        let channel_id = transfer.open_channel(&connection_id).unwrap();
+        let timeout = app.get_block_time_nanos() as u64 + 3_600_000_000_000;
+        let res = app
+            .execute_cosmos_msgs::<MsgTransferResponse>(
+                &[CosmosMsg::Ibc(IbcMsg::Transfer {
+                    channel_id: channel_id.clone(),
+                    to_address: "cosmos1receiver".to_string(),
+                    amount: Coin::new(1_000u128, "untrn"),
+                    timeout: IbcTimeout::with_timestamp(Timestamp::from_nanos(timeout)),
+                    memo: Some("memo".to_string()),
+                })],
+                &signer,
+            )
+            .unwrap();
+
+        let packets = transfer
+            .relay(
+                &res.events,
+                &PacketOutcome::transfer_success(),
+                &signer.address(),
+            )
+            .unwrap();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].sequence, res.data.sequence);
+        assert_eq!(packets[0].source_channel, channel_id);
+        assert_eq!(packets[0].timeout_timestamp, timeout);
+
+        let data: Value = serde_json::from_slice(&packets[0].data).unwrap();
+        assert_eq!(data["denom"], "untrn");
+        assert_eq!(data["amount"], "1000");
+        assert_eq!(data["sender"], signer.address());
+        assert_eq!(data["receiver"], "cosmos1receiver");
+        assert_eq!(data["memo"], "memo");
+    }
 }
@@ -0,0 +1,587 @@
+//! Translation of the `NeutronMsg` a contract emits to Neutron's native messages.
+
+use cosmwasm_std::{Coin, CosmosMsg, Uint128};
+use margined_neutron_std::shim::{Any, Timestamp};
+use margined_neutron_std::types::{
+    cosmos::bank::v1beta1::{DenomUnit, Metadata},
+    ibc::{applications::transfer::v1::MsgTransfer, core::client::v1::Height},
+    neutron::{
+        contractmanager::MsgResubmitFailure,
+        cron::{self, MsgAddSchedule, MsgRemoveSchedule},
+        dex,
+        feerefunder::Fee,
+        interchainqueries::{
+            KvKey, MsgRegisterInterchainQuery, MsgRemoveInterchainQueryRequest,
+            MsgUpdateInterchainQueryRequest,
+        },
+        interchaintxs::v1::{MsgRegisterInterchainAccount, MsgSubmitTx},
+    },
+    osmosis::tokenfactory::v1beta1::{
+        MsgBurn, MsgChangeAdmin, MsgCreateDenom, MsgForceTransfer, MsgMint, MsgSetBeforeSendHook,
+        MsgSetDenomMetadata,
+    },
+};
+use neutron_sdk::bindings::dex::msg::DexMsg;
+use neutron_sdk::bindings::msg::{ChannelOrdering, IbcFee, NeutronMsg};
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::{RunnerExecuteResult, RunnerResult};
+use test_tube_ntrn::runner::Runner;
+use test_tube_ntrn::utils::msg_to_any;
+use test_tube_ntrn::{Account, SigningAccount};
+
+use crate::NeutronTestApp;
+
+impl NeutronTestApp {
+    /// Execute the messages a contract would emit, `NeutronMsg` included, as `signer`.
+    ///
+    /// Messages restricted to contracts (interchain accounts and queries) or to the chain
+    /// authority (cron schedules) are rejected by the chain as they would be for `signer`.
+    /// `NeutronMsg::SubmitAdminProposal` is not part of a tx, execute it with
+    /// [`crate::Admin::submit_admin_proposal`].
+    pub fn execute_neutron_msgs<S>(
+        &self,
+        msgs: &[CosmosMsg<NeutronMsg>],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<S>
+    where
+        S: ::prost::Message + Default,
+    {
+        self.execute_cosmos_msgs_with(msgs, signer, neutron_msg_to_any)
+    }
+}
+
+/// Translate `msg` to the Neutron message handling it, sent by `signer`.
+pub fn neutron_msg_to_any(msg: &NeutronMsg, signer: &SigningAccount) -> RunnerResult<cosmrs::Any> {
+    let sender = signer.address();
+
+    match msg.clone() {
+        NeutronMsg::RegisterInterchainAccount {
+            connection_id,
+            interchain_account_id,
+            register_fee,
+            ordering,
+        } => msg_to_any(
+            "/neutron.interchaintxs.v1.MsgRegisterInterchainAccount",
+            &MsgRegisterInterchainAccount {
+                from_address: sender,
+                connection_id,
+                interchain_account_id,
+                register_fee: coins(register_fee.unwrap_or_default()),
+                ordering: match ordering {
+                    None => 0,
+                    Some(ChannelOrdering::OrderUnordered) => 1,
+                    Some(ChannelOrdering::OrderOrdered) => 2,
+                },
+            },
+        ),
+        NeutronMsg::SubmitTx {
+            connection_id,
+            interchain_account_id,
+            msgs,
+            memo,
+            timeout,
+            fee,
+        } => msg_to_any(
+            "/neutron.interchaintxs.v1.MsgSubmitTx",
+            &MsgSubmitTx {
+                from_address: sender,
+                interchain_account_id,
+                connection_id,
+                msgs: msgs
+                    .into_iter()
+                    .map(|msg| Any {
+                        type_url: msg.type_url,
+                        value: msg.value.to_vec(),
+                    })
+                    .collect(),
+                memo,
+                timeout,
+                fee: Some(ibc_fee(fee)),
+            },
+        ),
+        NeutronMsg::RegisterInterchainQuery {
+            query_type,
+            keys,
+            transactions_filter,
+            connection_id,
+            update_period,
+        } => msg_to_any(
+            "/neutron.interchainqueries.MsgRegisterInterchainQuery",
+            &MsgRegisterInterchainQuery {
+                query_type,
+                keys: keys
+                    .into_iter()
+                    .map(|key| KvKey {
+                        path: key.path,
+                        key: key.key.to_vec(),
+                    })
+                    .collect(),
+                transactions_filter,
+                connection_id,
+                update_period,
+                sender,
+            },
+        ),
+        NeutronMsg::UpdateInterchainQuery {
+            query_id,
+            new_keys,
+            new_update_period,
+            new_transactions_filter,
+        } => msg_to_any(
+            "/neutron.interchainqueries.MsgUpdateInterchainQueryRequest",
+            &MsgUpdateInterchainQueryRequest {
+                query_id,
+                new_keys: new_keys
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|key| KvKey {
+                        path: key.path,
+                        key: key.key.to_vec(),
+                    })
+                    .collect(),
+                new_update_period: new_update_period.unwrap_or_default(),
+                new_transactions_filter: new_transactions_filter.unwrap_or_default(),
+                sender,
+            },
+        ),
+        NeutronMsg::RemoveInterchainQuery { query_id } => msg_to_any(
+            "/neutron.interchainqueries.MsgRemoveInterchainQueryRequest",
+            &MsgRemoveInterchainQueryRequest { query_id, sender },
+        ),
+        NeutronMsg::IbcTransfer {
+            source_port,
+            source_channel,
+            token,
+            sender: _,
+            receiver,
+            timeout_height,
+            timeout_timestamp,
+            memo,
+            fee,
+        } => msg_to_any(
+            "/ibc.applications.transfer.v1.MsgTransfer",
+            &MsgTransfer {
+                source_port,
+                source_channel,
+                token: Some(token.into()),
+                sender,
+                receiver,
+                timeout_height: Some(Height {
+                    revision_number: timeout_height.revision_number.unwrap_or_default(),
+                    revision_height: timeout_height.revision_height.unwrap_or_default(),
+                }),
+                timeout_timestamp,
+                memo,
+                fee: Some(ibc_fee(fee)),
+            },
+        ),
+        // Admin proposals are executed by the admin module rather than in the tx of the contract.
+        NeutronMsg::SubmitAdminProposal { .. } => Err(RunnerError::ExecuteError {
+            msg: "NeutronMsg::SubmitAdminProposal is executed with Admin::submit_admin_proposal"
+                .to_string(),
+        }),
+        NeutronMsg::CreateDenom { subdenom } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgCreateDenom",
+            &MsgCreateDenom { sender, subdenom },
+        ),
+        NeutronMsg::ChangeAdmin {
+            denom,
+            new_admin_address,
+        } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgChangeAdmin",
+            &MsgChangeAdmin {
+                sender,
+                denom,
+                new_admin: new_admin_address,
+            },
+        ),
+        NeutronMsg::MintTokens {
+            denom,
+            amount,
+            mint_to_address,
+        } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgMint",
+            &MsgMint {
+                sender,
+                amount: Some(Coin::new(amount, denom).into()),
+                mint_to_address,
+            },
+        ),
+        NeutronMsg::BurnTokens {
+            denom,
+            amount,
+            burn_from_address,
+        } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgBurn",
+            &MsgBurn {
+                sender,
+                amount: Some(Coin::new(amount, denom).into()),
+                burn_from_address,
+            },
+        ),
+        NeutronMsg::SetBeforeSendHook {
+            denom,
+            contract_addr,
+        } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgSetBeforeSendHook",
+            &MsgSetBeforeSendHook {
+                sender,
+                denom,
+                contract_addr,
+            },
+        ),
+        NeutronMsg::ForceTransfer {
+            denom,
+            amount,
+            transfer_from_address,
+            transfer_to_address,
+        } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgForceTransfer",
+            &MsgForceTransfer {
+                sender,
+                amount: Some(Coin::new(amount, denom).into()),
+                transfer_from_address,
+                transfer_to_address,
+            },
+        ),
+        NeutronMsg::SetDenomMetadata {
+            description,
+            denom_units,
+            base,
+            display,
+            name,
+            symbol,
+            uri,
+            uri_hash,
+        } => msg_to_any(
+            "/osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata",
+            &MsgSetDenomMetadata {
+                sender,
+                metadata: Some(Metadata {
+                    description,
+                    denom_units: denom_units
+                        .into_iter()
+                        .map(|unit| DenomUnit {
+                            denom: unit.denom,
+                            exponent: unit.exponent,
+                            aliases: unit.aliases,
+                        })
+                        .collect(),
+                    base,
+                    display,
+                    name,
+                    symbol,
+                    uri,
+                    uri_hash,
+                }),
+            },
+        ),
+        NeutronMsg::AddSchedule {
+            name,
+            period,
+            msgs,
+            execution_stage,
+        } => msg_to_any(
+            "/neutron.cron.MsgAddSchedule",
+            &MsgAddSchedule {
+                authority: sender,
+                name,
+                period,
+                msgs: msgs
+                    .into_iter()
+                    .map(|msg| cron::MsgExecuteContract {
+                        contract: msg.contract,
+                        msg: msg.msg,
+                    })
+                    .collect(),
+                execution_stage: cron_execution_stage(&execution_stage)? as i32,
+            },
+        ),
+        NeutronMsg::RemoveSchedule { name } => msg_to_any(
+            "/neutron.cron.MsgRemoveSchedule",
+            &MsgRemoveSchedule {
+                authority: sender,
+                name,
+            },
+        ),
+        NeutronMsg::ResubmitFailure { failure_id } => msg_to_any(
+            "/neutron.contractmanager.MsgResubmitFailure",
+            &MsgResubmitFailure { sender, failure_id },
+        ),
+        NeutronMsg::Dex(msg) => dex_msg_to_any(msg, sender),
+    }
+}
+
+fn dex_msg_to_any(msg: DexMsg, creator: String) -> RunnerResult<cosmrs::Any> {
+    match msg {
+        DexMsg::Deposit {
+            receiver,
+            token_a,
+            token_b,
+            amounts_a,
+            amounts_b,
+            tick_indexes_a_to_b,
+            fees,
+            options,
+        } => msg_to_any(
+            "/neutron.dex.MsgDeposit",
+            &dex::MsgDeposit {
+                creator,
+                receiver,
+                token_a,
+                token_b,
+                amounts_a: amounts(amounts_a),
+                amounts_b: amounts(amounts_b),
+                tick_indexes_a_to_b,
+                fees,
+                options: options
+                    .into_iter()
+                    .map(|option| dex::DepositOptions {
+                        disable_autoswap: option.disable_swap,
+                        ..Default::default()
+                    })
+                    .collect(),
+            },
+        ),
+        DexMsg::Withdrawal {
+            receiver,
+            token_a,
+            token_b,
+            shares_to_remove,
+            tick_indexes_a_to_b,
+            fees,
+        } => msg_to_any(
+            "/neutron.dex.MsgWithdrawal",
+            &dex::MsgWithdrawal {
+                creator,
+                receiver,
+                token_a,
+                token_b,
+                shares_to_remove: amounts(shares_to_remove),
+                tick_indexes_a_to_b,
+                fees,
+            },
+        ),
+        DexMsg::PlaceLimitOrder {
+            receiver,
+            token_in,
+            token_out,
+            tick_index_in_to_out,
+            amount_in,
+            order_type,
+            expiration_time,
+            max_amount_out,
+            limit_sell_price,
+        } => msg_to_any(
+            "/neutron.dex.MsgPlaceLimitOrder",
+            &dex::MsgPlaceLimitOrder {
+                creator,
+                receiver,
+                token_in,
+                token_out,
+                tick_index_in_to_out,
+                amount_in: amount_in.to_string(),
+                order_type: order_type as i32,
+                expiration_time: expiration_time.map(|seconds| Timestamp {
+                    seconds: seconds as i64,
+                    nanos: 0,
+                }),
+                max_amount_out: max_amount_out
+                    .map(|amount| amount.to_string())
+                    .unwrap_or_default(),
+                limit_sell_price,
+                min_average_sell_price: String::new(),
+            },
+        ),
+        DexMsg::WithdrawFilledLimitOrder { tranche_key } => msg_to_any(
+            "/neutron.dex.MsgWithdrawFilledLimitOrder",
+            &dex::MsgWithdrawFilledLimitOrder {
+                creator,
+                tranche_key,
+            },
+        ),
+        DexMsg::CancelLimitOrder { tranche_key } => msg_to_any(
+            "/neutron.dex.MsgCancelLimitOrder",
+            &dex::MsgCancelLimitOrder {
+                creator,
+                tranche_key,
+            },
+        ),
+        DexMsg::MultiHopSwap {
+            receiver,
+            routes,
+            amount_in,
+            exit_limit_price,
+            pick_best_route,
+        } => msg_to_any(
+            "/neutron.dex.MsgMultiHopSwap",
+            &dex::MsgMultiHopSwap {
+                creator,
+                receiver,
+                routes: routes
+                    .into_iter()
+                    .map(|route| dex::MultiHopRoute { hops: route.hops })
+                    .collect(),
+                amount_in: amount_in.to_string(),
+                exit_limit_price: exit_limit_price.i,
+                pick_best_route,
+            },
+        ),
+    }
+}
+
+/// Schedules without execution stage run in the end blocker.
+fn cron_execution_stage(stage: &str) -> RunnerResult<cron::ExecutionStage> {
+    if stage.is_empty() {
+        return Ok(cron::ExecutionStage::EndBlocker);
+    }
+
+    cron::ExecutionStage::from_str_name(stage).ok_or_else(|| RunnerError::ExecuteError {
+        msg: format!("unknown cron execution stage `{}`", stage),
+    })
+}
+
+fn ibc_fee(fee: IbcFee) -> Fee {
+    Fee {
+        recv_fee: coins(fee.recv_fee),
+        ack_fee: coins(fee.ack_fee),
+        timeout_fee: coins(fee.timeout_fee),
+    }
+}
+
+fn coins(coins: Vec<Coin>) -> Vec<margined_neutron_std::types::cosmos::base::v1beta1::Coin> {
+    coins.into_iter().map(Into::into).collect()
+}
+
+fn amounts(amounts: Vec<Uint128>) -> Vec<String> {
+    amounts.iter().map(Uint128::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Coin, CosmosMsg, Uint128};
+    use margined_neutron_std::shim::Any;
+    use margined_neutron_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use margined_neutron_std::types::neutron::cron::{ExecutionStage, MsgAddSchedule};
+    use margined_neutron_std::types::neutron::interchaintxs::v1::MsgRegisterInterchainAccountResponse;
+    use margined_neutron_std::types::osmosis::tokenfactory::v1beta1::{
+        MsgCreateDenomResponse, MsgMintResponse,
+    };
+    use neutron_sdk::bindings::msg::{MsgExecuteContract, NeutronMsg};
+    use prost::Message;
+
+    use super::neutron_msg_to_any;
+    use crate::{Account, Admin, Bank, Cron, NeutronTestApp};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn execute_neutron_msgs() {
+        let app = NeutronTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let denom = app
+            .execute_neutron_msgs::<MsgCreateDenomResponse>(
+                &[CosmosMsg::Custom(NeutronMsg::CreateDenom {
+                    subdenom: "ureplay".to_string(),
+                })],
+                &signer,
+            )
+            .unwrap()
+            .data
+            .new_token_denom;
+        assert_eq!(denom, format!("factory/{}/ureplay", signer.address()));
+
+        let receiver = app.init_account(&[]).unwrap();
+        app.execute_neutron_msgs::<MsgMintResponse>(
+            &[
+                CosmosMsg::Custom(NeutronMsg::MintTokens {
+                    denom: denom.clone(),
+                    amount: Uint128::new(1_000),
+                    mint_to_address: signer.address(),
+                }),
+                CosmosMsg::Custom(NeutronMsg::ForceTransfer {
+                    denom: denom.clone(),
+                    amount: Uint128::new(400),
+                    transfer_from_address: signer.address(),
+                    transfer_to_address: receiver.address(),
+                }),
+            ],
+            &signer,
+        )
+        .unwrap();
+
+        let balance = Bank::new(&app)
+            .query_balance(&QueryBalanceRequest {
+                address: receiver.address(),
+                denom,
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "400");
+
+        // interchain accounts can only be registered by contracts
+        let err = app
+            .execute_neutron_msgs::<MsgRegisterInterchainAccountResponse>(
+                &[CosmosMsg::Custom(NeutronMsg::RegisterInterchainAccount {
+                    connection_id: "connection-0".to_string(),
+                    interchain_account_id: "test".to_string(),
+                    register_fee: None,
+                    ordering: None,
+                })],
+                &signer,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("is not a contract address"));
+    }
+
+    #[test]
+    fn replay_add_schedule_execution_stage() {
+        let app = NeutronTestApp::new();
+        let admin = Admin::new(&app);
+        let signer = app
+            .init_account(&[Coin::new(1_000_000_000u128, "untrn")])
+            .unwrap();
+
+        let add_schedule = |name: &str, execution_stage: &str| NeutronMsg::AddSchedule {
+            name: name.to_string(),
+            period: 5,
+            msgs: vec![MsgExecuteContract {
+                contract: signer.address(),
+                msg: "{}".to_string(),
+            }],
+            execution_stage: execution_stage.to_string(),
+        };
+        let translate = |msg: NeutronMsg| {
+            let any = neutron_msg_to_any(&msg, &signer).unwrap();
+            MsgAddSchedule::decode(any.value.as_slice()).unwrap()
+        };
+
+        // schedules without stage run in the end blocker, unknown stages are refused
+        assert_eq!(
+            translate(add_schedule("default", "")).execution_stage,
+            ExecutionStage::EndBlocker as i32
+        );
+        let err = neutron_msg_to_any(&add_schedule("unknown", "EXECUTION_STAGE_NEVER"), &signer)
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown cron execution stage"));
+
+        // replay the schedule with the cron authority as signer
+        let mut msg = translate(add_schedule("begin", "EXECUTION_STAGE_BEGIN_BLOCKER"));
+        msg.authority = admin.authority().unwrap();
+        admin
+            .execute_as_admin(vec![Any {
+                type_url: "/neutron.cron.MsgAddSchedule".to_string(),
+                value: msg.encode_to_vec(),
+            }])
+            .unwrap();
+
+        let schedule = Cron::new(&app).schedule("begin").unwrap();
+        assert_eq!(
+            schedule.execution_stage,
+            ExecutionStage::BeginBlocker as i32
+        );
+    }
+}
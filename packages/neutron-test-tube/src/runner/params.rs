@@ -0,0 +1,165 @@
+use margined_neutron_std::shim::Any;
+use margined_neutron_std::types::{
+    cosmos::{bank::v1beta1 as bank, gov::v1 as gov},
+    cosmwasm::wasm::v1 as wasm,
+    neutron::{
+        cron, dex, feeburner, feerefunder, interchainqueries, interchaintxs::v1 as interchaintxs,
+    },
+    osmosis::tokenfactory::v1beta1 as tokenfactory,
+    slinky::marketmap::v1 as marketmap,
+};
+use prost::Message;
+use test_tube_ntrn::runner::error::RunnerError;
+use test_tube_ntrn::runner::result::RunnerResult;
+
+/// Params of a module updated through a `MsgUpdateParams`-style message signed by the
+/// module authority (the admin module on Neutron).
+///
+/// See [`NeutronTestApp::update_params`](crate::NeutronTestApp::update_params) and
+/// [`NeutronTestApp::get_params`](crate::NeutronTestApp::get_params).
+pub trait ModuleParams: Message + Default + Sized {
+    type QueryRequest: Message + Default;
+    type QueryResponse: Message + Default;
+
+    /// Type url of the message updating the params
+    const UPDATE_TYPE_URL: &'static str;
+
+    /// gRPC path of the params query
+    const QUERY_PATH: &'static str;
+
+    /// Encode the update message for `params`, signed by `authority`.
+    fn update_msg(self, authority: String) -> Any;
+
+    /// Extract the params from the query response.
+    fn from_response(res: Self::QueryResponse) -> RunnerResult<Self>;
+}
+
+macro_rules! impl_module_params {
+    ($module:ident: $update:ident[$update_url:expr], $req:ident => $res:ident[$query_path:expr]) => {
+        impl ModuleParams for $module::Params {
+            type QueryRequest = $module::$req;
+            type QueryResponse = $module::$res;
+
+            const UPDATE_TYPE_URL: &'static str = $update_url;
+            const QUERY_PATH: &'static str = $query_path;
+
+            fn update_msg(self, authority: String) -> Any {
+                Any {
+                    type_url: Self::UPDATE_TYPE_URL.to_string(),
+                    value: $module::$update {
+                        authority,
+                        params: Some(self),
+                    }
+                    .encode_to_vec(),
+                }
+            }
+
+            fn from_response(res: Self::QueryResponse) -> RunnerResult<Self> {
+                res.params.ok_or_else(|| {
+                    RunnerError::GenericError(format!("no params returned by {}", $query_path))
+                })
+            }
+        }
+    };
+    ($module:ident: $update:ident[$update_url:expr], $query_path:expr) => {
+        impl_module_params!($module: $update[$update_url], QueryParamsRequest => QueryParamsResponse[$query_path]);
+    };
+}
+
+impl_module_params!(bank: MsgUpdateParams["/cosmos.bank.v1beta1.MsgUpdateParams"], "/cosmos.bank.v1beta1.Query/Params");
+impl_module_params!(gov: MsgUpdateParams["/cosmos.gov.v1.MsgUpdateParams"], "/cosmos.gov.v1.Query/Params");
+impl_module_params!(wasm: MsgUpdateParams["/cosmwasm.wasm.v1.MsgUpdateParams"], "/cosmwasm.wasm.v1.Query/Params");
+impl_module_params!(tokenfactory: MsgUpdateParams["/osmosis.tokenfactory.v1beta1.MsgUpdateParams"], "/osmosis.tokenfactory.v1beta1.Query/Params");
+impl_module_params!(dex: MsgUpdateParams["/neutron.dex.MsgUpdateParams"], "/neutron.dex.Query/Params");
+impl_module_params!(cron: MsgUpdateParams["/neutron.cron.MsgUpdateParams"], "/neutron.cron.Query/Params");
+impl_module_params!(feeburner: MsgUpdateParams["/neutron.feeburner.MsgUpdateParams"], "/neutron.feeburner.Query/Params");
+impl_module_params!(feerefunder: MsgUpdateParams["/neutron.feerefunder.MsgUpdateParams"], "/neutron.feerefunder.Query/Params");
+impl_module_params!(interchainqueries: MsgUpdateParams["/neutron.interchainqueries.MsgUpdateParams"], "/neutron.interchainqueries.Query/Params");
+impl_module_params!(interchaintxs: MsgUpdateParams["/neutron.interchaintxs.v1.MsgUpdateParams"], "/neutron.interchaintxs.v1.Query/Params");
+impl_module_params!(marketmap: MsgParams["/slinky.marketmap.v1.MsgParams"], ParamsRequest => ParamsResponse["/slinky.marketmap.v1.Query/Params"]);
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use cosmwasm_std::Coin;
+    use margined_neutron_std::types::{
+        cosmos::{bank::v1beta1 as bank, gov::v1 as gov},
+        cosmwasm::wasm::v1 as wasm,
+        neutron::{
+            cron, dex, feeburner, feerefunder, interchainqueries,
+            interchaintxs::v1 as interchaintxs,
+        },
+        osmosis::tokenfactory::v1beta1 as tokenfactory,
+        slinky::marketmap::v1 as marketmap,
+    };
+
+    use super::ModuleParams;
+    use crate::{Account, NeutronTestApp};
+
+    /// Change the current params of a module with `update` and check the module returns
+    /// exactly the updated params afterwards.
+    fn round_trip<P>(app: &NeutronTestApp, update: impl FnOnce(&mut P))
+    where
+        P: ModuleParams + Clone + PartialEq + Debug,
+    {
+        let before = app.get_params::<P>().unwrap();
+        let mut params = before.clone();
+        update(&mut params);
+        assert_ne!(params, before);
+
+        app.update_params(params.clone()).unwrap();
+        assert_eq!(app.get_params::<P>().unwrap(), params);
+    }
+
+    #[test]
+    fn update_and_get_params() {
+        let app = NeutronTestApp::new();
+        let account = app
+            .init_account(&[Coin::new(1_000_000_000u128, "untrn")])
+            .unwrap();
+
+        round_trip(&app, |params: &mut dex::Params| {
+            assert!(!params.paused);
+            params.paused = true;
+        });
+        round_trip(&app, |params: &mut bank::Params| {
+            params.default_send_enabled = !params.default_send_enabled;
+        });
+        round_trip(&app, |params: &mut gov::Params| {
+            params.burn_vote_veto = !params.burn_vote_veto;
+        });
+        round_trip(&app, |params: &mut wasm::Params| {
+            params.instantiate_default_permission =
+                if params.instantiate_default_permission == wasm::AccessType::Nobody as i32 {
+                    wasm::AccessType::Everybody as i32
+                } else {
+                    wasm::AccessType::Nobody as i32
+                };
+        });
+        round_trip(&app, |params: &mut tokenfactory::Params| {
+            params.denom_creation_gas_consume += 1;
+        });
+        round_trip(&app, |params: &mut cron::Params| {
+            params.limit += 1;
+        });
+        round_trip(&app, |params: &mut feeburner::Params| {
+            params.treasury_address = account.address();
+        });
+        round_trip(&app, |params: &mut feerefunder::Params| {
+            let min_fee = params.min_fee.as_mut().unwrap();
+            for coin in &mut min_fee.ack_fee {
+                coin.amount = (coin.amount.parse::<u128>().unwrap() + 1).to_string();
+            }
+        });
+        round_trip(&app, |params: &mut interchainqueries::Params| {
+            params.query_submit_timeout += 1;
+        });
+        round_trip(&app, |params: &mut interchaintxs::Params| {
+            params.msg_submit_tx_max_messages += 1;
+        });
+        round_trip(&app, |params: &mut marketmap::Params| {
+            params.admin = account.address();
+        });
+    }
+}
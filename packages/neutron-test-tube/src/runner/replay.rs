@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use test_tube_ntrn::runner::app::SlinkyPrices;
+use test_tube_ntrn::runner::error::{DecodeError, RunnerError};
+use test_tube_ntrn::runner::result::RunnerResult;
+
+use crate::{NeutronTestApp, BLOCK_TIME_SECONDS};
+
+/// Single row of a price history: the price of `base/quote` at `timestamp` (in seconds).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PricePoint {
+    pub timestamp: u64,
+    pub pair: String,
+    pub price: u128,
+}
+
+impl PricePoint {
+    fn to_slinky_price(&self) -> RunnerResult<SlinkyPrices> {
+        let (base, quote) = self.pair.split_once('/').ok_or_else(|| {
+            RunnerError::GenericError(format!(
+                "invalid pair `{}`, expected `BASE/QUOTE`",
+                self.pair
+            ))
+        })?;
+
+        Ok(SlinkyPrices {
+            base: base.trim().to_string(),
+            quote: quote.trim().to_string(),
+            price: self.price,
+        })
+    }
+}
+
+/// Replays a recorded oracle price history against a `NeutronTestApp`.
+///
+/// Rows sharing a timestamp are pushed in the same block. Between two timestamps the chain
+/// time is increased so that the next block lands on the recorded offset; rows closer together
+/// than [`BLOCK_TIME_SECONDS`] are applied in consecutive blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceFeedReplay {
+    points: Vec<PricePoint>,
+}
+
+impl PriceFeedReplay {
+    pub fn new(mut points: Vec<PricePoint>) -> Self {
+        points.sort_by_key(|p| p.timestamp);
+        Self { points }
+    }
+
+    /// Load a price history from a `.csv` or `.json` file, based on its extension.
+    pub fn from_file(path: impl AsRef<Path>) -> RunnerResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            RunnerError::GenericError(format!("unable to read {}: {}", path.display(), e))
+        })?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("csv") => Self::from_csv(&contents),
+            _ => Err(RunnerError::GenericError(format!(
+                "unsupported price history format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parse a JSON array of `{ "timestamp": .., "pair": "BASE/QUOTE", "price": .. }` objects.
+    pub fn from_json(contents: &str) -> RunnerResult<Self> {
+        let points: Vec<PricePoint> =
+            serde_json::from_str(contents).map_err(DecodeError::JsonDecodeError)?;
+
+        Ok(Self::new(points))
+    }
+
+    /// Parse `timestamp,pair,price` rows. A header row and blank lines are skipped.
+    pub fn from_csv(contents: &str) -> RunnerResult<Self> {
+        let mut points = vec![];
+
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (idx == 0 && line.starts_with("timestamp")) {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [timestamp, pair, price] = columns[..] else {
+                return Err(RunnerError::GenericError(format!(
+                    "line {}: expected `timestamp,pair,price`, got `{}`",
+                    idx + 1,
+                    line
+                )));
+            };
+
+            let parse_err = |column: &str| {
+                RunnerError::GenericError(format!("line {}: invalid {}", idx + 1, column))
+            };
+
+            points.push(PricePoint {
+                timestamp: timestamp.parse().map_err(|_| parse_err("timestamp"))?,
+                pair: pair.to_string(),
+                price: price.parse().map_err(|_| parse_err("price"))?,
+            });
+        }
+
+        Ok(Self::new(points))
+    }
+
+    pub fn points(&self) -> &[PricePoint] {
+        &self.points
+    }
+
+    /// Push every price into the oracle, one block per timestamp.
+    ///
+    /// `hook` is called after each block with the recorded timestamp and the prices that were
+    /// just set, so that contracts can be poked (liquidations, funding, ...) between blocks.
+    pub fn run<F>(&self, app: &NeutronTestApp, mut hook: F) -> RunnerResult<()>
+    where
+        F: FnMut(&NeutronTestApp, u64, &[SlinkyPrices]) -> RunnerResult<()>,
+    {
+        let mut blocks: BTreeMap<u64, Vec<SlinkyPrices>> = BTreeMap::new();
+        for point in &self.points {
+            blocks
+                .entry(point.timestamp)
+                .or_default()
+                .push(point.to_slinky_price()?);
+        }
+
+        let mut previous: Option<u64> = None;
+        for (timestamp, prices) in blocks {
+            if let Some(previous) = previous {
+                let elapsed = timestamp - previous;
+                if elapsed > BLOCK_TIME_SECONDS {
+                    app.advance_block(elapsed - BLOCK_TIME_SECONDS)?;
+                }
+            }
+
+            app.set_slinky_prices(&prices);
+            hook(app, timestamp, &prices)?;
+
+            previous = Some(timestamp);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use margined_neutron_std::types::slinky::{
+        oracle::v1::GetPriceRequest, types::v1::CurrencyPair,
+    };
+
+    use super::PriceFeedReplay;
+    use crate::{NeutronTestApp, Slinky};
+    use test_tube_ntrn::Module;
+
+    #[test]
+    fn price_feed_replay() {
+        let app = NeutronTestApp::new();
+        let slinky = Slinky::new(&app);
+
+        let path = std::env::temp_dir().join("neutron-test-tube-price-feed.csv");
+        std::fs::write(
+            &path,
+            "timestamp,pair,price\n0,ATOM/USDT,500000000\n60,ATOM/USDT,450000000\n120,ATOM/USDT,300000000\n",
+        )
+        .unwrap();
+
+        let replay = PriceFeedReplay::from_file(&path).unwrap();
+        assert_eq!(replay.points().len(), 3);
+
+        let start = app.get_block_time_seconds();
+        let mut seen = vec![];
+
+        replay
+            .run(&app, |app, timestamp, _| {
+                let price = slinky
+                    .get_price(&GetPriceRequest {
+                        currency_pair: Some(CurrencyPair {
+                            base: "ATOM".to_string(),
+                            quote: "USDT".to_string(),
+                        }),
+                    })?
+                    .price
+                    .unwrap()
+                    .price;
+
+                seen.push((timestamp, price));
+                assert_eq!(app.get_block_time_seconds() - start, timestamp as i64 + 3);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            seen,
+            vec![
+                (0, "500000000".to_string()),
+                (60, "450000000".to_string()),
+                (120, "300000000".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Test helper driving the `sudo-recorder` contract of `test_contracts/`, which dispatches
+//! messages as itself and records the sudo callbacks it receives.
+
+use cosmwasm_std::{Coin, CosmosMsg};
+use margined_neutron_std::types::cosmwasm::wasm::v1::MsgExecuteContractResponse;
+use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_sdk::sudo::msg::SudoMsg;
+use serde_json::json;
+
+use crate::{NeutronTestApp, RunnerExecuteResult, SigningAccount, Wasm};
+use test_tube_ntrn::Module;
+
+const ARTIFACT: &str = "./test_artifacts/sudo_recorder.wasm";
+
+pub struct SudoRecorder<'a> {
+    wasm: Wasm<'a, NeutronTestApp>,
+    pub address: String,
+}
+
+impl<'a> SudoRecorder<'a> {
+    /// Store and instantiate the contract, with `funds` to pay deposits and IBC fees.
+    pub fn new(app: &'a NeutronTestApp, funds: &[Coin], signer: &SigningAccount) -> Self {
+        let wasm = Wasm::new(app);
+        let wasm_byte_code = std::fs::read(ARTIFACT).unwrap_or_else(|e| {
+            panic!(
+                "{}: {}, build it with `test_contracts/build.sh`",
+                ARTIFACT, e
+            )
+        });
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, signer)
+            .unwrap()
+            .data
+            .code_id;
+        let address = wasm
+            .instantiate(
+                code_id,
+                &json!({}),
+                None,
+                Some("sudo recorder"),
+                funds,
+                signer,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        SudoRecorder { wasm, address }
+    }
+
+    pub fn dispatch(
+        &self,
+        msgs: Vec<CosmosMsg<NeutronMsg>>,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgExecuteContractResponse> {
+        self.wasm.execute(
+            &self.address,
+            &json!({ "dispatch": { "msgs": msgs } }),
+            &[],
+            signer,
+        )
+    }
+
+    pub fn set_sudo_failure(&self, fail: bool, signer: &SigningAccount) {
+        self.wasm
+            .execute(
+                &self.address,
+                &json!({ "set_sudo_failure": { "fail": fail } }),
+                &[],
+                signer,
+            )
+            .unwrap();
+    }
+
+    /// Successful sudo calls so far, oldest first.
+    pub fn sudo_calls(&self) -> Vec<SudoMsg> {
+        self.wasm
+            .query(&self.address, &json!({ "sudo_calls": {} }))
+            .unwrap()
+    }
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Build the test contracts with the CosmWasm optimizer and copy them to `test_artifacts/`,
+# e.g. `test_contracts/sudo-recorder` to `test_artifacts/sudo_recorder.wasm`.
+set -euo pipefail
+
+cd "$(dirname "$0")"
+
+for contract in */; do
+  contract="${contract%/}"
+
+  docker run --rm -v "$(pwd)/${contract}":/code \
+    --mount type=volume,source="${contract}_cache",target=/target \
+    --mount type=volume,source=registry_cache,target=/usr/local/cargo/registry \
+    cosmwasm/optimizer:0.16.0
+
+  cp "${contract}/artifacts/${contract//-/_}.wasm" ../test_artifacts/
+done
@@ -0,0 +1,84 @@
+//! Contract used by the tests of the IBC related modules: it dispatches messages with itself
+//! as sender (e.g. to register interchain queries and accounts, or to send IBC transfers with
+//! fees) and records every sudo callback it receives.
+//!
+//! Built into `test_artifacts/sudo_recorder.wasm` by `test_contracts/build.sh`.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    entry_point, to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult,
+};
+use cw_storage_plus::Item;
+use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_sdk::sudo::msg::SudoMsg;
+
+const SUDO_CALLS: Item<Vec<SudoMsg>> = Item::new("sudo_calls");
+const FAIL_SUDO: Item<bool> = Item::new("fail_sudo");
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Dispatch `msgs` with the contract as sender.
+    Dispatch { msgs: Vec<CosmosMsg<NeutronMsg>> },
+    /// Make the following sudo calls fail, or succeed again.
+    SetSudoFailure { fail: bool },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    /// Every successful sudo call, oldest first, as a `Vec<SudoMsg>`.
+    SudoCalls {},
+}
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    SUDO_CALLS.save(deps.storage, &vec![])?;
+    FAIL_SUDO.save(deps.storage, &false)?;
+
+    Ok(Response::new())
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    match msg {
+        ExecuteMsg::Dispatch { msgs } => Ok(Response::new().add_messages(msgs)),
+        ExecuteMsg::SetSudoFailure { fail } => {
+            FAIL_SUDO.save(deps.storage, &fail)?;
+            Ok(Response::new())
+        }
+    }
+}
+
+#[entry_point]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
+    if FAIL_SUDO.load(deps.storage)? {
+        return Err(StdError::generic_err("sudo failure"));
+    }
+
+    SUDO_CALLS.update(deps.storage, |mut calls| -> StdResult<_> {
+        calls.push(msg);
+        Ok(calls)
+    })?;
+
+    Ok(Response::new())
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::SudoCalls {} => to_json_binary(&SUDO_CALLS.load(deps.storage)?),
+    }
+}
@@ -2,7 +2,7 @@ use cosmrs::{
     crypto::{secp256k1::SigningKey, PublicKey},
     AccountId,
 };
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, Decimal};
 
 pub trait Account {
     fn public_key(&self) -> PublicKey;
@@ -20,6 +20,8 @@ pub struct SigningAccount {
     prefix: String,
     signing_key: SigningKey,
     fee_setting: FeeSetting,
+    fee_granter: Option<String>,
+    fee_payer: Option<Box<SigningAccount>>,
 }
 
 impl SigningAccount {
@@ -28,15 +30,13 @@ impl SigningAccount {
             prefix,
             signing_key,
             fee_setting,
+            fee_granter: None,
+            fee_payer: None,
         }
     }
 
     pub fn with_prefix(self, prefix: String) -> Self {
-        Self {
-            prefix,
-            signing_key: self.signing_key,
-            fee_setting: self.fee_setting,
-        }
+        Self { prefix, ..self }
     }
 
     pub fn fee_setting(&self) -> &FeeSetting {
@@ -45,9 +45,32 @@ impl SigningAccount {
 
     pub fn with_fee_setting(self, fee_setting: FeeSetting) -> Self {
         Self {
-            prefix: self.prefix,
-            signing_key: self.signing_key,
             fee_setting,
+            ..self
+        }
+    }
+
+    /// Account whose fee allowance pays the fees of the txs signed by this account.
+    pub fn fee_granter(&self) -> Option<&str> {
+        self.fee_granter.as_deref()
+    }
+
+    pub fn with_fee_granter(self, fee_granter: impl Into<String>) -> Self {
+        Self {
+            fee_granter: Some(fee_granter.into()),
+            ..self
+        }
+    }
+
+    /// Account paying the fees of the txs signed by this account. It co-signs every tx.
+    pub fn fee_payer(&self) -> Option<&SigningAccount> {
+        self.fee_payer.as_deref()
+    }
+
+    pub fn with_fee_payer(self, fee_payer: SigningAccount) -> Self {
+        Self {
+            fee_payer: Some(Box::new(fee_payer)),
+            ..self
         }
     }
 }
@@ -116,12 +139,32 @@ impl Account for NonSigningAccount {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FeeSetting {
+    /// Simulate the tx and pay a fixed `gas_price`, whatever the fee market currently asks.
+    /// Accounts created by the app default to [`FeeSetting::Market`] instead.
     Auto {
         gas_price: Coin,
         gas_adjustment: f64,
     },
+    /// Simulate the tx and pay the current fee market gas price for `denom`, times
+    /// `price_multiplier`. Denoms other than the native one are priced by the chain.
+    Market {
+        denom: String,
+        gas_adjustment: f64,
+        price_multiplier: Decimal,
+    },
     Custom {
         amount: Coin,
         gas_limit: u64,
     },
 }
+
+impl FeeSetting {
+    /// Denom the fees are paid in.
+    pub fn denom(&self) -> &str {
+        match self {
+            FeeSetting::Auto { gas_price, .. } => &gas_price.denom,
+            FeeSetting::Market { denom, .. } => denom,
+            FeeSetting::Custom { amount, .. } => &amount.denom,
+        }
+    }
+}
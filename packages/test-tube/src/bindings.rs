@@ -218,11 +218,95 @@ extern "C" {
     pub fn FinalizeBlock(envId: GoUint64, tx: GoString) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
-    pub fn IncreaseTime(envId: GoUint64, seconds: GoInt64);
+    pub fn IncreaseTime(envId: GoUint64, seconds: GoInt64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn AdvanceBlock(envId: GoUint64, seconds: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SubmitKVQueryResult(
+        envId: GoUint64,
+        queryId: GoUint64,
+        base64QueryResultBytes: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SubmitTxQueryResult(
+        envId: GoUint64,
+        queryId: GoUint64,
+        revisionNumber: GoUint64,
+        revisionHeight: GoUint64,
+        base64Data: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn CreateMockConnection(
+        envId: GoUint64,
+        counterpartyChainId: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn OpenAckChannel(
+        envId: GoUint64,
+        portId: GoString,
+        channelId: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn OpenChannel(
+        envId: GoUint64,
+        portId: GoString,
+        connectionId: GoString,
+        version: GoString,
+        ordered: GoUint8,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn AcknowledgePacket(
+        envId: GoUint64,
+        packetJson: GoString,
+        base64Ack: GoString,
+        relayer: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn ReceivePacket(
+        envId: GoUint64,
+        packetJson: GoString,
+        relayer: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SetDenomMetadata(
+        envId: GoUint64,
+        base64MetadataBytes: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SetUpgradeHandler(envId: GoUint64, name: GoString);
+}
+extern "C" {
+    pub fn TimeoutPacket(
+        envId: GoUint64,
+        packetJson: GoString,
+        relayer: GoString,
+    ) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
     pub fn SetSlinkyPrices(envId: GoUint64, pricesJson: GoString);
 }
+extern "C" {
+    pub fn AdminProposalMessage(envId: GoUint64, msgJson: GoString) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn ExecuteAsAdmin(
+        envId: GoUint64,
+        base64ProposalBytes: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn GetModuleAddress(envId: GoUint64, moduleName: GoString) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn Query(
         envId: GoUint64,
@@ -230,6 +314,14 @@ extern "C" {
         base64QueryMsgBytes: GoString,
     ) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn QueryAtHeight(
+        envId: GoUint64,
+        height: GoInt64,
+        path: GoString,
+        base64QueryMsgBytes: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn AccountSequence(envId: GoUint64, bech32Address: GoString) -> GoUint64;
 }
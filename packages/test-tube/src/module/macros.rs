@@ -40,6 +40,30 @@ macro_rules! fn_execute {
 
 #[macro_export]
 macro_rules! fn_query {
+    (pub $name:ident [$path:expr]: $req:ty => $res:ty, all_pages: $all_pages:ident => $items:ident) => {
+        $crate::fn_query! {
+            pub $name [$path]: $req => $res
+        }
+
+        /// Query every page, following `next_key`, and return a response holding the
+        /// items of all of them. The `pagination` of `msg` is ignored.
+        #[allow(clippy::field_reassign_with_default)]
+        pub fn $all_pages(&self, msg: &$req) -> $crate::RunnerResult<$res> {
+            let items = $crate::paginate(
+                |pagination| {
+                    let mut msg = msg.clone();
+                    msg.pagination = Some(pagination);
+                    self.$name(&msg)
+                },
+                |res: $res| (res.$items, res.pagination),
+            )
+            .collect::<$crate::RunnerResult<Vec<_>>>()?;
+
+            let mut res = <$res>::default();
+            res.$items = items;
+            Ok(res)
+        }
+    };
     (pub $name:ident [$path:expr]: $req:ty => $res:ty) => {
         pub fn $name(&self, msg: &$req) -> $crate::RunnerResult<$res> {
             self.runner.query::<$req, $res>($path, msg)
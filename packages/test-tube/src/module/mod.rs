@@ -2,6 +2,9 @@ use crate::runner::Runner;
 
 #[macro_use]
 pub mod macros;
+pub mod pagination;
+
+pub use pagination::{paginate, PAGE_LIMIT};
 
 pub trait Module<'a, R: Runner<'a>> {
     fn new(runner: &'a R) -> Self;
@@ -0,0 +1,76 @@
+use cosmrs::proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+use prost::Message;
+
+use crate::runner::error::{DecodeError, RunnerError};
+use crate::runner::result::RunnerResult;
+
+/// Number of items requested per page, the default limit of the SDK.
+pub const PAGE_LIMIT: u64 = 100;
+
+/// Iterate over the items of every page of a paginated query, following `next_key`.
+///
+/// `query` gets the page request to set as the `pagination` of the query request, and
+/// `items` splits a response into its items and `pagination`:
+///
+/// ```ignore
+/// let balances = paginate(
+///     |pagination| {
+///         bank.query_all_balances(&QueryAllBalancesRequest {
+///             address: address.clone(),
+///             pagination: Some(pagination),
+///             resolve_denom: false,
+///         })
+///     },
+///     |res| (res.balances, res.pagination),
+/// )
+/// .collect::<RunnerResult<Vec<_>>>()?;
+/// ```
+///
+/// Iteration stops after the first error.
+pub fn paginate<P, Res, PR, T>(
+    mut query: impl FnMut(P) -> RunnerResult<Res>,
+    mut items: impl FnMut(Res) -> (Vec<T>, Option<PR>),
+) -> impl Iterator<Item = RunnerResult<T>>
+where
+    P: Message + Default,
+    PR: Message,
+{
+    let mut next_key = Some(vec![]);
+    let mut page = Vec::new().into_iter();
+
+    std::iter::from_fn(move || loop {
+        if let Some(item) = page.next() {
+            return Some(Ok(item));
+        }
+
+        let key = next_key.take()?;
+        let page_res = convert::<_, P>(&PageRequest {
+            key,
+            limit: PAGE_LIMIT,
+            ..Default::default()
+        })
+        .and_then(&mut query)
+        .and_then(|res| {
+            let (res_items, pagination) = items(res);
+            let pagination = pagination
+                .map(|p| convert::<_, PageResponse>(&p))
+                .transpose()?;
+            Ok((res_items, pagination))
+        });
+
+        match page_res {
+            Ok((res_items, pagination)) => {
+                next_key = pagination.map(|p| p.next_key).filter(|key| !key.is_empty());
+                page = res_items.into_iter();
+            }
+            Err(e) => return Some(Err(e)),
+        }
+    })
+}
+
+/// Page types are the same messages in every proto crate, convert them through their encoding.
+fn convert<F: Message, T: Message + Default>(from: &F) -> RunnerResult<T> {
+    T::decode(from.encode_to_vec().as_slice())
+        .map_err(DecodeError::ProtoDecodeError)
+        .map_err(RunnerError::DecodeError)
+}
@@ -3,22 +3,30 @@ use std::ffi::CString;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine as _;
 use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxMsgData;
 use cosmrs::proto::tendermint::v0_38::abci::ResponseFinalizeBlock;
 use cosmrs::tx;
 use cosmrs::tx::{Fee, SignerInfo};
 use cosmrs::Any;
-use cosmwasm_std::{Coin, Timestamp};
+use cosmwasm_std::{Attribute, Coin, DecCoin, Decimal, Event, Timestamp, Uint128};
 use prost::Message;
 use serde::Serialize;
 
 use crate::account::{Account, FeeSetting, SigningAccount};
 use crate::bindings::{
-    AccountNumber, AccountSequence, FinalizeBlock, GetBlockHeight, GetBlockTime, GetParamSet,
-    GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime, InitAccount, InitTestEnv, Query,
-    SetParamSet, SetSlinkyPrices, Simulate,
+    AccountNumber, AccountSequence, AcknowledgePacket, AdminProposalMessage, AdvanceBlock,
+    CreateMockConnection, ExecuteAsAdmin, FinalizeBlock, GetBlockHeight, GetBlockTime,
+    GetModuleAddress, GetParamSet, GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime,
+    InitAccount, InitTestEnv, OpenAckChannel, OpenChannel, Query, QueryAtHeight, ReceivePacket,
+    SetDenomMetadata, SetParamSet, SetSlinkyPrices, SetUpgradeHandler, Simulate,
+    SubmitKVQueryResult, SubmitTxQueryResult, TimeoutPacket,
 };
 use crate::redefine_as_go_string;
 use crate::runner::error::{DecodeError, EncodeError, RunnerError};
+use crate::runner::feemarket::{
+    dec_coin_from_proto, GasPriceRequest, GasPriceResponse, GasPricesRequest, GasPricesResponse,
+    GAS_PRICES_QUERY_PATH, GAS_PRICE_QUERY_PATH,
+};
 use crate::runner::result::RawResult;
 use crate::runner::result::{RunnerExecuteResult, RunnerResult};
 use crate::runner::Runner;
@@ -59,9 +67,60 @@ impl BaseApp {
     }
 
     /// Increase the time of the blockchain by the given number of seconds.
+    ///
+    /// Panics if the block cannot be finalized, e.g. while halted at an upgrade plan.
+    /// Use [`BaseApp::advance_block`] to get the error instead.
     pub fn increase_time(&self, seconds: u64) {
         unsafe {
-            IncreaseTime(self.id, seconds.try_into().unwrap());
+            let res = IncreaseTime(self.id, seconds.try_into().unwrap());
+            RawResult::from_non_null_ptr(res)
+                .into_result()
+                .unwrap_or_else(|e| panic!("cannot increase time: {}", e));
+        }
+    }
+
+    /// Finalize the block following a state change made outside of a tx.
+    unsafe fn next_block(&self) -> RunnerResult<()> {
+        let res = IncreaseTime(self.id, 1);
+        RawResult::from_non_null_ptr(res).into_result()?;
+
+        Ok(())
+    }
+
+    /// Finalize an empty block `seconds` after the current one and return the events
+    /// emitted outside of transactions (begin/end blockers).
+    ///
+    /// Returns an error, and stays at the current height, if the block cannot be
+    /// finalized, e.g. when an upgrade plan without handler is reached.
+    pub fn advance_block(&self, seconds: u64) -> RunnerResult<Vec<Event>> {
+        let res = unsafe {
+            let res = AdvanceBlock(self.id, seconds);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        let res =
+            ResponseFinalizeBlock::decode(res.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+
+        Ok(res
+            .events
+            .into_iter()
+            .map(|e| {
+                Event::new(e.r#type).add_attributes(
+                    e.attributes
+                        .into_iter()
+                        .map(|a| Attribute::new(a.key, a.value)),
+                )
+            })
+            .collect())
+    }
+
+    /// Register an upgrade handler for the plan `name`, unless the app already has one,
+    /// so that the chain resumes once the plan height is reached.
+    pub fn set_upgrade_handler(&self, name: &str) {
+        redefine_as_go_string!(name);
+
+        unsafe {
+            SetUpgradeHandler(self.id, name);
         }
     }
 
@@ -126,15 +185,244 @@ impl BaseApp {
         let validator = SigningAccount::new(
             self.address_prefix.to_string(),
             signing_key,
-            FeeSetting::Auto {
-                gas_price: Coin::new(NEUTRON_MIN_GAS_PRICE, denom),
+            FeeSetting::Market {
+                denom,
                 gas_adjustment,
+                price_multiplier: Decimal::one(),
             },
         );
 
         Ok(validator)
     }
 
+    /// Get the bech32 address of a module account
+    pub fn get_module_address(&self, module_name: &str) -> RunnerResult<String> {
+        redefine_as_go_string!(module_name);
+
+        let addr = unsafe {
+            let addr = GetModuleAddress(self.id, module_name);
+            CString::from_raw(addr)
+        }
+        .to_str()
+        .map_err(DecodeError::Utf8Error)?
+        .to_string();
+
+        Ok(addr)
+    }
+
+    /// Decode the JSON encoded message of an admin `ProposalExecuteMessage`, e.g.
+    /// `{"@type":"/cosmos.bank.v1beta1.MsgUpdateParams",...}`, to protobuf.
+    pub fn admin_proposal_message(&self, msg_json: &str) -> RunnerResult<Any> {
+        redefine_as_go_string!(msg_json);
+
+        let res = unsafe {
+            let res = AdminProposalMessage(self.id, msg_json);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        Ok(Any::decode(res.as_slice()).map_err(DecodeError::ProtoDecodeError)?)
+    }
+
+    /// Execute the messages of an admin module `MsgSubmitProposal` and return the
+    /// response of each message, in order.
+    pub fn execute_admin_proposal<M: Message>(&self, proposal: &M) -> RunnerResult<Vec<Any>> {
+        unsafe {
+            let proposal = BASE64_STANDARD.encode(Message::encode_to_vec(proposal));
+            redefine_as_go_string!(proposal);
+            let res = ExecuteAsAdmin(self.id, proposal);
+
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+            let tx_msg_data =
+                TxMsgData::decode(res.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+
+            Ok(tx_msg_data
+                .msg_responses
+                .into_iter()
+                .map(|res| Any {
+                    type_url: res.type_url,
+                    value: res.value,
+                })
+                .collect())
+        }
+    }
+
+    /// Store the result of a registered KV interchain query without verifying proofs.
+    /// `query_result` is a `neutron.interchainqueries.QueryResult`.
+    pub fn submit_kv_query_result<M: Message>(
+        &self,
+        query_id: u64,
+        query_result: &M,
+    ) -> RunnerResult<()> {
+        unsafe {
+            let query_result = BASE64_STANDARD.encode(Message::encode_to_vec(query_result));
+            redefine_as_go_string!(query_result);
+            let res = SubmitKVQueryResult(self.id, query_id, query_result);
+
+            RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Deliver a transaction found on the remote chain at `revision_number`/`revision_height`
+    /// to the owner of a registered TX interchain query.
+    pub fn submit_tx_query_result(
+        &self,
+        query_id: u64,
+        revision_number: u64,
+        revision_height: u64,
+        data: &[u8],
+    ) -> RunnerResult<()> {
+        unsafe {
+            let data = BASE64_STANDARD.encode(data);
+            redefine_as_go_string!(data);
+            let res =
+                SubmitTxQueryResult(self.id, query_id, revision_number, revision_height, data);
+
+            RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the bank metadata of any denom. `metadata` is a `cosmos.bank.v1beta1.Metadata`.
+    pub fn set_denom_metadata<M: Message>(&self, metadata: &M) -> RunnerResult<()> {
+        unsafe {
+            let metadata = BASE64_STANDARD.encode(Message::encode_to_vec(metadata));
+            redefine_as_go_string!(metadata);
+            let res = SetDenomMetadata(self.id, metadata);
+
+            RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Create a client and an open connection to a mock counterparty chain,
+    /// returning the connection id.
+    pub fn create_mock_connection(&self, counterparty_chain_id: &str) -> RunnerResult<String> {
+        redefine_as_go_string!(counterparty_chain_id);
+
+        unsafe {
+            let res = CreateMockConnection(self.id, counterparty_chain_id);
+
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+            Ok(String::from_utf8(res).map_err(|e| DecodeError::Utf8Error(e.utf8_error()))?)
+        }
+    }
+
+    /// Complete the handshake of a channel in `INIT` state on behalf of the mock counterparty.
+    pub fn open_ack_channel(&self, port_id: &str, channel_id: &str) -> RunnerResult<()> {
+        redefine_as_go_string!(port_id, channel_id);
+
+        unsafe {
+            let res = OpenAckChannel(self.id, port_id, channel_id);
+
+            RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Open a channel on `port_id` over a mock connection and return its id. The mock
+    /// counterparty uses the same port and channel id.
+    pub fn open_channel(
+        &self,
+        port_id: &str,
+        connection_id: &str,
+        version: &str,
+        ordered: bool,
+    ) -> RunnerResult<String> {
+        redefine_as_go_string!(port_id, connection_id, version);
+
+        unsafe {
+            let res = OpenChannel(self.id, port_id, connection_id, version, ordered.into());
+
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+            Ok(String::from_utf8(res).map_err(|e| DecodeError::Utf8Error(e.utf8_error()))?)
+        }
+    }
+
+    /// Deliver the acknowledgement `ack` of a packet sent by this chain.
+    pub fn acknowledge_packet<P: Serialize>(
+        &self,
+        packet: &P,
+        ack: &[u8],
+        relayer: &str,
+    ) -> RunnerResult<()> {
+        let packet = serde_json::to_string(packet).map_err(EncodeError::JsonEncodeError)?;
+        let ack = BASE64_STANDARD.encode(ack);
+        redefine_as_go_string!(packet, ack, relayer);
+
+        unsafe {
+            let res = AcknowledgePacket(self.id, packet, ack, relayer);
+
+            RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Deliver a packet from the mock counterparty and return the acknowledgement written
+    /// by the application (empty if asynchronous). The sequence and source of the packet
+    /// are taken from the destination channel.
+    pub fn receive_packet<P: Serialize>(&self, packet: &P, relayer: &str) -> RunnerResult<Vec<u8>> {
+        let packet = serde_json::to_string(packet).map_err(EncodeError::JsonEncodeError)?;
+        redefine_as_go_string!(packet, relayer);
+
+        unsafe {
+            let res = ReceivePacket(self.id, packet, relayer);
+
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+
+            Ok(res)
+        }
+    }
+
+    /// Time out a packet sent by this chain.
+    pub fn timeout_packet<P: Serialize>(&self, packet: &P, relayer: &str) -> RunnerResult<()> {
+        let packet = serde_json::to_string(packet).map_err(EncodeError::JsonEncodeError)?;
+        redefine_as_go_string!(packet, relayer);
+
+        unsafe {
+            let res = TimeoutPacket(self.id, packet, relayer);
+
+            RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+        }
+
+        Ok(())
+    }
+
     /// Get the current block time
     pub fn get_block_timestamp(&self) -> Timestamp {
         let result = unsafe { GetBlockTime(self.id) };
@@ -167,8 +455,9 @@ impl BaseApp {
 
         let base64_priv = unsafe {
             let addr = InitAccount(self.id, coins_json);
-            FinalizeBlock(self.id, empty_tx);
-            CString::from_raw(addr)
+            let addr = CString::from_raw(addr);
+            RawResult::from_non_null_ptr(FinalizeBlock(self.id, empty_tx)).into_result()?;
+            addr
         }
         .to_str()
         .map_err(DecodeError::Utf8Error)?
@@ -186,9 +475,10 @@ impl BaseApp {
         Ok(SigningAccount::new(
             self.address_prefix.clone(),
             signing_key,
-            FeeSetting::Auto {
-                gas_price: Coin::new(NEUTRON_MIN_GAS_PRICE, self.fee_denom.clone()),
+            FeeSetting::Market {
+                denom: self.fee_denom.clone(),
                 gas_adjustment: self.default_gas_adjustment,
+                price_multiplier: Decimal::one(),
             },
         ))
     }
@@ -203,45 +493,72 @@ impl BaseApp {
         &self,
         msgs: I,
         signer: &SigningAccount,
-        fee: Fee,
+        mut fee: Fee,
     ) -> RunnerResult<Vec<u8>>
     where
         I: IntoIterator<Item = cosmrs::Any>,
     {
         let tx_body = tx::Body::new(msgs, "", 0u32);
-        let addr = signer.address();
-
-        redefine_as_go_string!(addr);
-
-        let seq = unsafe { AccountSequence(self.id, addr) };
-
-        let account_number = unsafe { AccountNumber(self.id, addr) };
-
-        let signer_info = SignerInfo::single_direct(Some(signer.public_key()), seq);
-        let auth_info = signer_info.auth_info(fee);
-        let sign_doc = tx::SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &(self
-                .chain_id
-                .parse()
-                .expect("parse const str of chain id should never fail")),
-            account_number,
-        )
-        .map_err(|e| match e.downcast::<prost::EncodeError>() {
-            Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
-            Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
-        })?;
 
-        let tx_raw = sign_doc.sign(signer.signing_key()).unwrap();
+        // the fee payer, if any, signs after the signer of the messages
+        let signers: Vec<&SigningAccount> =
+            std::iter::once(signer).chain(signer.fee_payer()).collect();
+
+        fee.granter = signer
+            .fee_granter()
+            .map(|granter| parse_account_id(granter, "fee granter"))
+            .transpose()?;
+        fee.payer = signer.fee_payer().map(|payer| payer.account_id());
+
+        let accounts = signers
+            .iter()
+            .map(|signer| {
+                let addr = signer.address();
+                redefine_as_go_string!(addr);
+
+                let seq = unsafe { AccountSequence(self.id, addr) };
+                let account_number = unsafe { AccountNumber(self.id, addr) };
+
+                (
+                    SignerInfo::single_direct(Some(signer.public_key()), seq),
+                    account_number,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let auth_info = tx::AuthInfo {
+            signer_infos: accounts.iter().map(|(info, _)| info.clone()).collect(),
+            fee,
+        };
 
-        tx_raw
-            .to_bytes()
-            .map_err(|e| match e.downcast::<prost::EncodeError>() {
-                Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
-                Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
+        let signatures = signers
+            .iter()
+            .zip(accounts.iter())
+            .map(|(signer, (_, account_number))| -> RunnerResult<Vec<u8>> {
+                let sign_doc = tx::SignDoc::new(
+                    &tx_body,
+                    &auth_info,
+                    &(self
+                        .chain_id
+                        .parse()
+                        .expect("parse const str of chain id should never fail")),
+                    *account_number,
+                )
+                .map_err(proto_encode_error)?;
+
+                let sign_doc_bytes = sign_doc.into_bytes().map_err(proto_encode_error)?;
+
+                Ok(signer.signing_key().sign(&sign_doc_bytes).unwrap().to_vec())
             })
-            .map_err(RunnerError::EncodeError)
+            .collect::<RunnerResult<Vec<Vec<u8>>>>()?;
+
+        let tx_raw = cosmrs::proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: tx_body.into_bytes().map_err(proto_encode_error)?,
+            auth_info_bytes: auth_info.into_bytes().map_err(proto_encode_error)?,
+            signatures,
+        };
+
+        Ok(tx_raw.encode_to_vec())
     }
 
     pub fn simulate_tx<I>(
@@ -252,10 +569,16 @@ impl BaseApp {
     where
         I: IntoIterator<Item = cosmrs::Any>,
     {
+        // denoms without a fee market price are left to fail in the simulation itself
+        let denom = signer.fee_setting().denom();
+        let gas_price = self
+            .query_gas_price(denom)
+            .map(|price| price.amount.to_uint_ceil().u128())
+            .unwrap_or_default();
         let zero_fee = Fee::from_amount_and_gas(
             cosmrs::Coin {
-                denom: self.fee_denom.parse().unwrap(),
-                amount: NEUTRON_MIN_GAS_PRICE,
+                denom: denom.parse().unwrap(),
+                amount: gas_price,
             },
             0u64,
         );
@@ -287,12 +610,28 @@ impl BaseApp {
                 let gas_limit = ((gas_info.gas_used as f64) * (gas_adjustment)).ceil() as u64;
 
                 let amount = cosmrs::Coin {
-                    denom: self.fee_denom.parse().unwrap(),
+                    denom: gas_price.denom.parse().unwrap(),
                     amount: (((gas_limit as f64) * (gas_price.amount.u128() as f64)).ceil() as u64)
                         .into(),
                 };
                 Ok(Fee::from_amount_and_gas(amount, gas_limit))
             }
+            FeeSetting::Market {
+                denom,
+                gas_adjustment,
+                price_multiplier,
+            } => {
+                let gas_price = self.query_gas_price(denom)?.amount * *price_multiplier;
+
+                let gas_info = self.simulate_tx(msgs, signer)?;
+                let gas_limit = ((gas_info.gas_used as f64) * (gas_adjustment)).ceil() as u64;
+
+                let amount = cosmrs::Coin {
+                    denom: denom.parse().unwrap(),
+                    amount: Uint128::from(gas_limit).mul_ceil(gas_price).u128(),
+                };
+                Ok(Fee::from_amount_and_gas(amount, gas_limit))
+            }
             FeeSetting::Custom { .. } => {
                 panic!("estimate fee is a private function and should never be called when fee_setting is Custom");
             }
@@ -301,6 +640,58 @@ impl BaseApp {
         res
     }
 
+    /// Current minimum gas price of the fee market for `denom`. Non-native denoms are
+    /// converted by the chain and fail if they are not accepted for fees.
+    pub fn query_gas_price(&self, denom: &str) -> RunnerResult<DecCoin> {
+        let res: GasPriceResponse = self.query(
+            GAS_PRICE_QUERY_PATH,
+            &GasPriceRequest {
+                denom: denom.to_string(),
+            },
+        )?;
+
+        let price = res.price.ok_or_else(|| RunnerError::QueryError {
+            msg: format!("no gas price for {}", denom),
+        })?;
+
+        dec_coin_from_proto(price)
+    }
+
+    /// Current minimum gas price of the fee market for every denom accepted for fees.
+    pub fn query_gas_prices(&self) -> RunnerResult<Vec<DecCoin>> {
+        let res: GasPricesResponse = self.query(GAS_PRICES_QUERY_PATH, &GasPricesRequest {})?;
+
+        res.prices.into_iter().map(dec_coin_from_proto).collect()
+    }
+
+    /// Same as `query`, but against the state committed at `height` instead of the latest one.
+    pub fn query_at_height<Q, R>(&self, height: u64, path: &str, q: &Q) -> RunnerResult<R>
+    where
+        Q: ::prost::Message,
+        R: ::prost::Message + Default,
+    {
+        let height = i64::try_from(height).map_err(|_| RunnerError::QueryError {
+            msg: format!("height {} out of range", height),
+        })?;
+
+        let mut buf = Vec::new();
+
+        Q::encode(q, &mut buf).map_err(EncodeError::ProtoEncodeError)?;
+
+        let base64_query_msg_bytes = BASE64_STANDARD.encode(buf);
+
+        redefine_as_go_string!(path);
+        redefine_as_go_string!(base64_query_msg_bytes);
+
+        unsafe {
+            let res = QueryAtHeight(self.id, height, path, base64_query_msg_bytes);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+            R::decode(res.as_slice())
+                .map_err(DecodeError::ProtoDecodeError)
+                .map_err(RunnerError::DecodeError)
+        }
+    }
+
     /// Set parameter set for a given subspace.
     pub fn set_param_set(&self, subspace: &str, pset: impl Into<Any>) -> RunnerResult<()> {
         unsafe {
@@ -310,11 +701,12 @@ impl BaseApp {
             redefine_as_go_string!(subspace);
             let res = SetParamSet(self.id, subspace, pset);
 
-            // Just move one block forward
-            IncreaseTime(self.id, 1u64.try_into().unwrap());
-
             // returns empty bytes if success
             RawResult::from_non_null_ptr(res).into_result()?;
+
+            // Just move one block forward
+            self.next_block()?;
+
             Ok(())
         }
     }
@@ -372,7 +764,9 @@ impl<'a> Runner<'a> for BaseApp {
     {
         unsafe {
             let fee = match &signer.fee_setting() {
-                FeeSetting::Auto { .. } => self.estimate_fee(msgs.clone(), signer)?,
+                FeeSetting::Auto { .. } | FeeSetting::Market { .. } => {
+                    self.estimate_fee(msgs.clone(), signer)?
+                }
                 FeeSetting::Custom { amount, gas_limit } => Fee::from_amount_and_gas(
                     cosmrs::Coin {
                         denom: amount.denom.parse().unwrap(),
@@ -421,3 +815,16 @@ impl<'a> Runner<'a> for BaseApp {
         }
     }
 }
+
+fn parse_account_id(address: &str, role: &str) -> RunnerResult<cosmrs::AccountId> {
+    address
+        .parse()
+        .map_err(|e| RunnerError::GenericError(format!("invalid {} `{}`: {}", role, address, e)))
+}
+
+fn proto_encode_error(e: cosmrs::ErrorReport) -> EncodeError {
+    match e.downcast::<prost::EncodeError>() {
+        Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
+        Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
+    }
+}
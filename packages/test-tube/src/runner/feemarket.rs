@@ -0,0 +1,48 @@
+//! Messages of the `feemarket.feemarket.v1` queries used to price transactions.
+
+use std::str::FromStr;
+
+use cosmrs::proto::cosmos::base::v1beta1::DecCoin as ProtoDecCoin;
+use cosmwasm_std::{DecCoin, Decimal, Uint128};
+
+use crate::runner::error::RunnerError;
+use crate::runner::result::RunnerResult;
+
+pub const GAS_PRICE_QUERY_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrice";
+pub const GAS_PRICES_QUERY_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrices";
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub price: ::core::option::Option<ProtoDecCoin>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesRequest {}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub prices: ::prost::alloc::vec::Vec<ProtoDecCoin>,
+}
+
+/// `sdk.DecCoin` amounts are sent over gRPC as atomics with 18 decimal places.
+pub(crate) fn dec_coin_from_proto(coin: ProtoDecCoin) -> RunnerResult<DecCoin> {
+    let invalid = |e: cosmwasm_std::StdError| RunnerError::QueryError {
+        msg: format!("invalid gas price `{}`: {}", coin.amount, e),
+    };
+
+    let amount = if coin.amount.contains('.') {
+        Decimal::from_str(&coin.amount).map_err(invalid)?
+    } else {
+        Decimal::new(Uint128::from_str(&coin.amount).map_err(invalid)?)
+    };
+
+    Ok(DecCoin::new(amount, coin.denom))
+}
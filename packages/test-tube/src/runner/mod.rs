@@ -2,11 +2,12 @@ use cosmwasm_std::CosmosMsg;
 
 use crate::account::SigningAccount;
 use crate::runner::result::{RunnerExecuteResult, RunnerResult};
-use crate::utils::{bank_msg_to_any, wasm_msg_to_any};
+use crate::utils::cosmos_msg_to_any;
 use crate::RunnerError;
 
 pub mod app;
 pub mod error;
+pub mod feemarket;
 pub mod result;
 
 pub trait Runner<'a> {
@@ -40,6 +41,12 @@ pub trait Runner<'a> {
     where
         R: ::prost::Message + Default;
 
+    /// Execute `msgs` as `signer`, translated to the SDK messages the chain would execute
+    /// for a contract. `CosmosMsg::Custom` messages are not supported.
+    ///
+    /// Messages that only a contract can send have no translation and return an error:
+    /// `BankMsg::Burn` and every `IbcMsg` but `IbcMsg::Transfer` (`SendPacket`,
+    /// `WriteAcknowledgement`, `CloseChannel`, `PayPacketFee` and `PayPacketFeeAsync`).
     fn execute_cosmos_msgs<S>(
         &self,
         msgs: &[CosmosMsg],
@@ -47,24 +54,29 @@ pub trait Runner<'a> {
     ) -> RunnerExecuteResult<S>
     where
         S: ::prost::Message + Default,
+    {
+        self.execute_cosmos_msgs_with(msgs, signer, |msg, _| {
+            Err(RunnerError::ExecuteError {
+                msg: format!("unsupported custom message: {:?}", msg),
+            })
+        })
+    }
+
+    /// Execute `msgs` as `signer`, translating `CosmosMsg::Custom` messages with
+    /// `custom_msg_to_any`.
+    fn execute_cosmos_msgs_with<S, C>(
+        &self,
+        msgs: &[CosmosMsg<C>],
+        signer: &SigningAccount,
+        custom_msg_to_any: impl Fn(&C, &SigningAccount) -> RunnerResult<cosmrs::Any>,
+    ) -> RunnerExecuteResult<S>
+    where
+        S: ::prost::Message + Default,
+        C: std::fmt::Debug,
     {
         let msgs = msgs
             .iter()
-            .map(|msg| match msg {
-                CosmosMsg::Bank(msg) => bank_msg_to_any(msg, signer),
-                #[allow(deprecated)]
-                CosmosMsg::Stargate { type_url, value } => Ok(cosmrs::Any {
-                    type_url: type_url.clone(),
-                    value: value.to_vec(),
-                }),
-                #[cfg(feature = "cosmwasm_2_0")]
-                CosmosMsg::Any(msg) => Ok(cosmrs::Any {
-                    type_url: msg.type_url.clone(),
-                    value: msg.value.to_vec(),
-                }),
-                CosmosMsg::Wasm(msg) => wasm_msg_to_any(msg, signer),
-                _ => todo!("unsupported cosmos msg variant"),
-            })
+            .map(|msg| cosmos_msg_to_any(msg, signer, &custom_msg_to_any))
             .collect::<Result<Vec<_>, RunnerError>>()?;
 
         self.execute_multiple_raw(msgs, signer)
@@ -1,11 +1,20 @@
 use cosmrs::proto::{
-    cosmos::bank::v1beta1::MsgSend,
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        distribution::v1beta1::{
+            MsgFundCommunityPool, MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+        },
+        gov::v1::{MsgVote, MsgVoteWeighted, WeightedVoteOption},
+        staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate},
+    },
     cosmwasm::wasm::v1::{
-        MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract,
-        MsgUpdateAdmin,
+        MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgInstantiateContract2,
+        MsgMigrateContract, MsgUpdateAdmin,
     },
 };
-use cosmwasm_std::{BankMsg, Coin, WasmMsg};
+use cosmwasm_std::{
+    BankMsg, Coin, CosmosMsg, DistributionMsg, GovMsg, IbcMsg, StakingMsg, VoteOption, WasmMsg,
+};
 use prost::Message;
 
 use crate::{Account, EncodeError, RunnerError, SigningAccount};
@@ -22,6 +31,13 @@ pub fn coins_to_proto(coins: &[Coin]) -> Vec<cosmrs::proto::cosmos::base::v1beta
         .collect()
 }
 
+pub fn coin_to_proto(coin: &Coin) -> cosmrs::proto::cosmos::base::v1beta1::Coin {
+    cosmrs::proto::cosmos::base::v1beta1::Coin {
+        denom: coin.denom.clone(),
+        amount: coin.amount.to_string(),
+    }
+}
+
 pub fn proto_coin_to_coin(proto_coin: &cosmrs::proto::cosmos::base::v1beta1::Coin) -> Coin {
     Coin {
         denom: proto_coin.denom.clone(),
@@ -55,9 +71,12 @@ pub fn bank_msg_to_any(msg: &BankMsg, signer: &SigningAccount) -> Result<cosmrs:
             };
             msg_to_any(type_url, &msg)
         }
-        _ => {
-            todo!() // TODO: Can't find BurnMsg...?
-        }
+        // The SDK has no message burning coins from an account, wasmd burns them from the
+        // contract through the bank keeper.
+        BankMsg::Burn { .. } => Err(RunnerError::ExecuteError {
+            msg: "BankMsg::Burn has no SDK message equivalent".to_string(),
+        }),
+        _ => Err(unsupported_msg(msg)),
     }
 }
 
@@ -124,8 +143,240 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
                 sender: signer.address(),
             },
         ),
-        _ => Err(RunnerError::ExecuteError {
-            msg: "Unsupported WasmMsg".to_string(),
+        WasmMsg::Instantiate2 {
+            admin,
+            code_id,
+            label,
+            msg,
+            funds,
+            salt,
+        } => msg_to_any(
+            "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+            &MsgInstantiateContract2 {
+                sender: signer.address(),
+                admin: admin.clone().unwrap_or_default(),
+                code_id: *code_id,
+                label: label.clone(),
+                msg: msg.to_vec(),
+                funds: coins_to_proto(funds),
+                salt: salt.to_vec(),
+                fix_msg: false,
+            },
+        ),
+        _ => Err(unsupported_msg(msg)),
+    }
+}
+
+pub fn staking_msg_to_any(
+    msg: &StakingMsg,
+    signer: &SigningAccount,
+) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        StakingMsg::Delegate { validator, amount } => msg_to_any(
+            "/cosmos.staking.v1beta1.MsgDelegate",
+            &MsgDelegate {
+                delegator_address: signer.address(),
+                validator_address: validator.clone(),
+                amount: Some(coin_to_proto(amount)),
+            },
+        ),
+        StakingMsg::Undelegate { validator, amount } => msg_to_any(
+            "/cosmos.staking.v1beta1.MsgUndelegate",
+            &MsgUndelegate {
+                delegator_address: signer.address(),
+                validator_address: validator.clone(),
+                amount: Some(coin_to_proto(amount)),
+            },
+        ),
+        StakingMsg::Redelegate {
+            src_validator,
+            dst_validator,
+            amount,
+        } => msg_to_any(
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+            &MsgBeginRedelegate {
+                delegator_address: signer.address(),
+                validator_src_address: src_validator.clone(),
+                validator_dst_address: dst_validator.clone(),
+                amount: Some(coin_to_proto(amount)),
+            },
+        ),
+        _ => Err(unsupported_msg(msg)),
+    }
+}
+
+pub fn distribution_msg_to_any(
+    msg: &DistributionMsg,
+    signer: &SigningAccount,
+) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        DistributionMsg::SetWithdrawAddress { address } => msg_to_any(
+            "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+            &MsgSetWithdrawAddress {
+                delegator_address: signer.address(),
+                withdraw_address: address.clone(),
+            },
+        ),
+        DistributionMsg::WithdrawDelegatorReward { validator } => msg_to_any(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            &MsgWithdrawDelegatorReward {
+                delegator_address: signer.address(),
+                validator_address: validator.clone(),
+            },
+        ),
+        DistributionMsg::FundCommunityPool { amount } => msg_to_any(
+            "/cosmos.distribution.v1beta1.MsgFundCommunityPool",
+            &MsgFundCommunityPool {
+                amount: coins_to_proto(amount),
+                depositor: signer.address(),
+            },
+        ),
+        _ => Err(unsupported_msg(msg)),
+    }
+}
+
+/// Only ICS-20 transfers can be sent by an account, the other IBC messages act on the
+/// channels owned by the contract port.
+pub fn ibc_msg_to_any(msg: &IbcMsg, signer: &SigningAccount) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        IbcMsg::Transfer {
+            channel_id,
+            to_address,
+            amount,
+            timeout,
+            memo,
+        } => msg_to_any(
+            "/ibc.applications.transfer.v1.MsgTransfer",
+            &MsgTransfer {
+                source_port: "transfer".to_string(),
+                source_channel: channel_id.clone(),
+                token: Some(coin_to_proto(amount)),
+                sender: signer.address(),
+                receiver: to_address.clone(),
+                timeout_height: Some(
+                    timeout
+                        .block()
+                        .map(|block| Height {
+                            revision_number: block.revision,
+                            revision_height: block.height,
+                        })
+                        .unwrap_or_default(),
+                ),
+                timeout_timestamp: timeout.timestamp().map(|t| t.nanos()).unwrap_or_default(),
+                memo: memo.clone().unwrap_or_default(),
+            },
+        ),
+        _ => Err(unsupported_msg(msg)),
+    }
+}
+
+pub fn gov_msg_to_any(msg: &GovMsg, signer: &SigningAccount) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        GovMsg::Vote {
+            proposal_id,
+            option,
+        } => msg_to_any(
+            "/cosmos.gov.v1.MsgVote",
+            &MsgVote {
+                proposal_id: *proposal_id,
+                voter: signer.address(),
+                option: vote_option_to_proto(option),
+                metadata: String::new(),
+            },
+        ),
+        GovMsg::VoteWeighted {
+            proposal_id,
+            options,
+        } => msg_to_any(
+            "/cosmos.gov.v1.MsgVoteWeighted",
+            &MsgVoteWeighted {
+                proposal_id: *proposal_id,
+                voter: signer.address(),
+                options: options
+                    .iter()
+                    .map(|o| WeightedVoteOption {
+                        option: vote_option_to_proto(&o.option),
+                        weight: o.weight.to_string(),
+                    })
+                    .collect(),
+                metadata: String::new(),
+            },
+        ),
+        _ => Err(unsupported_msg(msg)),
+    }
+}
+
+/// Translate `msg` to the SDK message the chain would execute for it, with `signer` in place
+/// of the contract. `CosmosMsg::Custom` messages are translated by `custom_msg_to_any`.
+pub fn cosmos_msg_to_any<C>(
+    msg: &CosmosMsg<C>,
+    signer: &SigningAccount,
+    custom_msg_to_any: impl Fn(&C, &SigningAccount) -> Result<cosmrs::Any, RunnerError>,
+) -> Result<cosmrs::Any, RunnerError>
+where
+    C: std::fmt::Debug,
+{
+    match msg {
+        CosmosMsg::Bank(msg) => bank_msg_to_any(msg, signer),
+        CosmosMsg::Custom(msg) => custom_msg_to_any(msg, signer),
+        CosmosMsg::Staking(msg) => staking_msg_to_any(msg, signer),
+        CosmosMsg::Distribution(msg) => distribution_msg_to_any(msg, signer),
+        #[allow(deprecated)]
+        CosmosMsg::Stargate { type_url, value } => Ok(cosmrs::Any {
+            type_url: type_url.clone(),
+            value: value.to_vec(),
         }),
+        CosmosMsg::Any(msg) => Ok(cosmrs::Any {
+            type_url: msg.type_url.clone(),
+            value: msg.value.to_vec(),
+        }),
+        CosmosMsg::Ibc(msg) => ibc_msg_to_any(msg, signer),
+        CosmosMsg::Wasm(msg) => wasm_msg_to_any(msg, signer),
+        CosmosMsg::Gov(msg) => gov_msg_to_any(msg, signer),
+        _ => Err(unsupported_msg(msg)),
     }
 }
+
+fn vote_option_to_proto(option: &VoteOption) -> i32 {
+    match option {
+        VoteOption::Yes => 1,
+        VoteOption::Abstain => 2,
+        VoteOption::No => 3,
+        VoteOption::NoWithVeto => 4,
+    }
+}
+
+fn unsupported_msg(msg: &impl std::fmt::Debug) -> RunnerError {
+    RunnerError::ExecuteError {
+        msg: format!("unsupported message: {:?}", msg),
+    }
+}
+
+// `ibc.applications.transfer.v1.MsgTransfer` is not part of the cosmos-sdk protos.
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    source_port: String,
+    #[prost(string, tag = "2")]
+    source_channel: String,
+    #[prost(message, optional, tag = "3")]
+    token: Option<cosmrs::proto::cosmos::base::v1beta1::Coin>,
+    #[prost(string, tag = "4")]
+    sender: String,
+    #[prost(string, tag = "5")]
+    receiver: String,
+    #[prost(message, optional, tag = "6")]
+    timeout_height: Option<Height>,
+    #[prost(uint64, tag = "7")]
+    timeout_timestamp: u64,
+    #[prost(string, tag = "8")]
+    memo: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct Height {
+    #[prost(uint64, tag = "1")]
+    revision_number: u64,
+    #[prost(uint64, tag = "2")]
+    revision_height: u64,
+}